@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(i) => write!(f, "{i}"),
+            Self::Str(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Int(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    pub fn test_answer_from() {
+        assert_eq!(Answer::from(42_u64), Answer::Int(42));
+        assert_eq!(Answer::from(-3_i64), Answer::Int(-3));
+        assert_eq!(Answer::from("ab"), Answer::Str("ab".to_string()));
+    }
+
+    #[test]
+    pub fn test_answer_display() {
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Answer::from("ab").to_string(), "ab");
+    }
+}
@@ -0,0 +1,226 @@
+use nom_supreme::error::{BaseErrorKind, Expectation, StackContext};
+use thiserror::Error;
+
+pub use nom_supreme::error::ErrorTree;
+
+pub type IResult<I, O> = nom::IResult<I, O, ErrorTree<I>>;
+
+#[derive(Debug, PartialEq, Error)]
+#[error("line {line}, column {column}: expected {expected}")]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error pointing at `location`, which has to be a slice of
+    /// `source`. Locations outside of `source` point at its end.
+    pub fn at(source: &str, location: &str, expected: impl Into<String>) -> Self {
+        let offset = (location.as_ptr() as usize)
+            .checked_sub(source.as_ptr() as usize)
+            .filter(|o| *o <= source.len())
+            .unwrap_or(source.len());
+
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        Self::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            expected,
+        )
+    }
+
+    pub fn from_nom(source: &str, err: nom::Err<ErrorTree<&str>>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let (location, expected) = deepest(&e);
+
+                Self::at(source, location, expected)
+            }
+            nom::Err::Incomplete(_) => Self::at(source, &source[source.len()..], "more input"),
+        }
+    }
+
+    /// Renders the error as an annotated snippet of `source`, the input it was
+    /// raised for: the offending line with a caret under the failing column.
+    pub fn render(&self, source: &str) -> String {
+        let text = source
+            .lines()
+            .nth(self.line.saturating_sub(1))
+            .unwrap_or("");
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        // keep tabs so the caret lines up with the rendered line
+        let indent: String = text
+            .chars()
+            .chain(std::iter::repeat(' '))
+            .take(self.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        format!(
+            "expected {expected}\n\
+             {gutter}--> line {line}, column {column}\n\
+             {gutter} |\n\
+             {number} | {text}\n\
+             {gutter} | {indent}^ expected {expected}",
+            expected = self.expected,
+            line = self.line,
+            column = self.column,
+        )
+    }
+}
+
+fn describe_base(
+    kind: &BaseErrorKind<&'static str, Box<dyn std::error::Error + Send + Sync>>,
+) -> String {
+    match kind {
+        BaseErrorKind::Expected(Expectation::Tag(t)) => format!("`{}`", t.escape_debug()),
+        BaseErrorKind::Expected(Expectation::Char(c)) => format!("`{}`", c.escape_debug()),
+        BaseErrorKind::Expected(Expectation::Eof) => "end of input".to_string(),
+        BaseErrorKind::Expected(Expectation::Something) => "more input".to_string(),
+        BaseErrorKind::Expected(e) => e.to_string(),
+        BaseErrorKind::Kind(k) => k.description().to_lowercase(),
+        BaseErrorKind::External(e) => e.to_string(),
+    }
+}
+
+/// Finds the location that got furthest into the input together with a
+/// description of what was expected there. Contexts attached by the parsers
+/// take precedence over the raw nom expectation.
+fn deepest<'a>(err: &ErrorTree<&'a str>) -> (&'a str, String) {
+    match err {
+        ErrorTree::Base { location, kind } => (location, describe_base(kind)),
+        ErrorTree::Stack { base, contexts } => {
+            let (location, expected) = deepest(base);
+
+            let context = contexts.iter().find_map(|(_, c)| match c {
+                StackContext::Context(c) => Some(c.to_string()),
+                StackContext::Kind(_) => None,
+            });
+
+            (location, context.unwrap_or(expected))
+        }
+        ErrorTree::Alt(alternatives) => {
+            let candidates: Vec<(&str, String)> = alternatives.iter().map(deepest).collect();
+            let furthest = candidates.iter().map(|(l, _)| l.len()).min().unwrap_or(0);
+
+            let mut expected: Vec<String> = vec![];
+            let mut location = "";
+            for (l, e) in candidates.into_iter().filter(|(l, _)| l.len() == furthest) {
+                location = l;
+                if !expected.contains(&e) {
+                    expected.push(e);
+                }
+            }
+
+            (location, expected.join(" or "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use nom::{
+        branch::alt,
+        character::complete::{self, space1},
+        error::context,
+        multi::separated_list1,
+    };
+    use nom_supreme::tag::complete::tag;
+
+    #[test]
+    pub fn test_at() {
+        let source = "ab\ncde\nf";
+
+        assert_eq!(
+            ParseError::at(source, &source[0..], "x"),
+            ParseError::new(1, 1, "x")
+        );
+        assert_eq!(
+            ParseError::at(source, &source[5..], "x"),
+            ParseError::new(2, 3, "x")
+        );
+        assert_eq!(
+            ParseError::at(source, &source[8..], "x"),
+            ParseError::new(3, 2, "x")
+        );
+        assert_eq!(
+            ParseError::at(source, "elsewhere", "x"),
+            ParseError::new(3, 2, "x")
+        );
+    }
+
+    #[test]
+    pub fn test_render() {
+        let source = "Card 1: 41 48 | 83 86\nCard 2: 13 32 x 61";
+        let err = ParseError::new(2, 15, "`|`");
+
+        assert_eq!(
+            err.render(source),
+            [
+                "expected `|`",
+                " --> line 2, column 15",
+                "  |",
+                "2 | Card 2: 13 32 x 61",
+                "  |               ^ expected `|`",
+            ]
+            .join("\n")
+        );
+
+        let err = ParseError::new(3, 1, "start tile `S`");
+        assert_eq!(err.render("..\n..\n").lines().nth(3), Some("3 | "),);
+
+        let err = ParseError::new(1, 5, "`)`");
+        assert_eq!(
+            err.render("\tA =").lines().last(),
+            Some("  | \t   ^ expected `)`")
+        );
+    }
+
+    #[test]
+    pub fn test_from_nom() {
+        let source = "Time: 1 2\nDist: 3";
+        let err = tag::<_, _, ErrorTree<&str>>("Time:")(&source[10..]).unwrap_err();
+
+        assert_eq!(
+            ParseError::from_nom(source, err),
+            ParseError::new(2, 1, "`Time:`")
+        );
+
+        let err = separated_list1(space1, complete::u64::<_, ErrorTree<&str>>)("a 2").unwrap_err();
+        assert_eq!(
+            ParseError::from_nom("a 2", err).to_string(),
+            "line 1, column 1: expected an ascii digit"
+        );
+    }
+
+    #[test]
+    pub fn test_from_nom_context_and_alt() {
+        let source = "1,x";
+        let err = context("number", complete::u64::<_, ErrorTree<&str>>)(&source[2..]).unwrap_err();
+
+        assert_eq!(
+            ParseError::from_nom(source, err),
+            ParseError::new(1, 3, "number")
+        );
+
+        let err = alt((tag::<_, _, ErrorTree<&str>>("L"), tag("R")))("X").unwrap_err();
+        assert_eq!(
+            ParseError::from_nom("X", err),
+            ParseError::new(1, 1, "`L` or `R`")
+        );
+    }
+}
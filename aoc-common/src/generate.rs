@@ -0,0 +1,35 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Produces random but valid puzzle input, so the solutions can be
+/// benchmarked on inputs much larger than the real one.
+pub trait Generator {
+    /// Generates input of the given `size`. What the size counts is up to
+    /// the puzzle, like lines or the side of a grid. The same `size` and
+    /// `seed` always produce the same input.
+    fn generate(size: usize, seed: u64) -> String;
+}
+
+/// The random number generator behind every [`Generator`]. Unlike the
+/// `rand` defaults its output is stable across platforms and releases.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use crate::*;
+
+    #[test]
+    pub fn test_seeded_rng() {
+        let draw = |seed| {
+            let mut rng = seeded_rng(seed);
+            (0..8).map(|_| rng.gen_range(0..100)).collect::<Vec<u32>>()
+        };
+
+        assert_eq!(draw(1), draw(1));
+        assert_ne!(draw(1), draw(2));
+    }
+}
@@ -0,0 +1,394 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::ParseError;
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
+}
+
+const DIAGONAL_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows. Fails on the first row whose length
+    /// differs from the first one.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseError> {
+        let width = rows.first().map(|r| r.len()).unwrap_or(0);
+        let height = rows.len();
+
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(ParseError::new(
+                y + 1,
+                row.len().min(width) + 1,
+                format!("row of {width} cells"),
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses one row per line, converting every character with `cell`.
+    /// Characters for which `cell` returns `None` are reported as not being
+    /// `expected`.
+    pub fn parse_with(
+        input: &str,
+        cell: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| cell(c).ok_or_else(|| ParseError::new(y + 1, x + 1, expected)))
+                    .collect::<Result<Vec<T>, ParseError>>()
+            })
+            .collect::<Result<Vec<Vec<T>>, ParseError>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.y * self.width + p.x])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        match self.contains(p) {
+            true => Some(&mut self.cells[p.y * self.width + p.x]),
+            false => None,
+        }
+    }
+
+    /// Moves `by` cells away from `p`, returning `None` when leaving the grid.
+    pub fn offset(&self, p: Point, by: (isize, isize)) -> Option<Point> {
+        let next = Point {
+            x: p.x.checked_add_signed(by.0)?,
+            y: p.y.checked_add_signed(by.1)?,
+        };
+
+        self.contains(next).then_some(next)
+    }
+
+    pub fn step(&self, p: Point, direction: Direction) -> Option<Point> {
+        self.offset(p, direction.offset())
+    }
+
+    /// The horizontal and vertical neighbours of `p` inside the grid.
+    pub fn neighbours(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(p, d))
+    }
+
+    /// All eight neighbours of `p` inside the grid, including the diagonals.
+    pub fn neighbours_diagonal(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        DIAGONAL_OFFSETS
+            .into_iter()
+            .filter_map(move |o| self.offset(p, o))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {x} outside of the grid");
+
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point { x, y }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, c)| predicate(c)).map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, turning columns into rows.
+    pub fn transpose(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_right(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flat_map(|c| c.rev()).cloned().collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    pub fn rotate_left(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|x| self.column(x))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        match self.get(p) {
+            Some(c) => c,
+            None => panic!(
+                "point ({}, {}) outside of the {}x{} grid",
+                p.x, p.y, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+
+        match self.get_mut(p) {
+            Some(c) => c,
+            None => panic!(
+                "point ({}, {}) outside of the {width}x{height} grid",
+                p.x, p.y
+            ),
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Some, "any character")
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}", row.iter().collect::<String>())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    pub fn test_parse() {
+        let grid = grid();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Point::new(1, 1)], 'e');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        assert_eq!(
+            "abc\nde\nfgh".parse::<Grid<char>>(),
+            Err(ParseError::new(2, 3, "row of 3 cells"))
+        );
+        assert_eq!(
+            Grid::parse_with("#.\n.x", |c| (c != 'x').then_some(c == '#'), "`#` or `.`"),
+            Err(ParseError::new(2, 2, "`#` or `.`"))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "point (0, 2) outside of the 3x2 grid")]
+    pub fn test_index_out_of_bounds() {
+        let _ = grid()[Point::new(0, 2)];
+    }
+
+    #[test]
+    pub fn test_neighbours() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.neighbours(Point::new(0, 0)).collect::<Vec<Point>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours_diagonal(Point::new(1, 1))
+                .map(|p| grid[p])
+                .collect::<String>(),
+            "abcdf"
+        );
+        assert_eq!(grid.step(Point::new(2, 1), Direction::East), None);
+        assert_eq!(
+            grid.step(Point::new(2, 1), Direction::North),
+            Some(Point::new(2, 0))
+        );
+    }
+
+    #[test]
+    pub fn test_rows_and_columns() {
+        let grid = grid();
+
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<String>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(grid.position(|c| *c == 'f'), Some(Point::new(2, 1)));
+    }
+
+    #[test]
+    pub fn test_transpose_and_rotate() {
+        let grid = grid();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    pub fn test_manhattan() {
+        assert_eq!(Point::new(1, 6).manhattan(&Point::new(5, 11)), 9);
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use thiserror::Error;
+
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("input file `{}` not found (pass a path, `-` for stdin or set {INPUT_ENV})", .0.display())]
+    NotFound(PathBuf),
+
+    #[error("cannot read input file `{}`: {source}", .path.display())]
+    File { path: PathBuf, source: io::Error },
+
+    #[error("cannot read input from stdin: {0}")]
+    Stdin(io::Error),
+}
+
+/// Expands to the path of `input.txt` next to the calling crate's manifest.
+#[macro_export]
+macro_rules! default_input {
+    () => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    };
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Resolves the source from an explicit argument, falling back to
+    /// `AOC_INPUT` and then to `default`. An argument of `-` selects stdin.
+    pub fn resolve(arg: Option<String>, default: impl Into<PathBuf>) -> Self {
+        match arg.or_else(|| env::var(INPUT_ENV).ok()) {
+            Some(a) if a == "-" => Self::Stdin,
+            Some(a) => Self::Path(a.into()),
+            None => Self::Path(default.into()),
+        }
+    }
+
+    pub fn from_env(default: impl Into<PathBuf>) -> Self {
+        Self::resolve(None, default)
+    }
+
+    pub fn read_to_string(&self) -> Result<String, InputError> {
+        match self {
+            Self::Path(path) => fs::read_to_string(path).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => InputError::NotFound(path.clone()),
+                _ => InputError::File {
+                    path: path.clone(),
+                    source: e,
+                },
+            }),
+            Self::Stdin => {
+                let mut buf = String::new();
+                io::stdin()
+                    .read_to_string(&mut buf)
+                    .map_err(InputError::Stdin)?;
+
+                Ok(buf)
+            }
+        }
+    }
+
+    pub fn read_lines(&self) -> Result<Vec<String>, InputError> {
+        Ok(self
+            .read_to_string()?
+            .lines()
+            .map(|l| l.to_string())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    pub fn test_resolve() {
+        assert_eq!(
+            InputSource::resolve(Some("-".to_string()), "input.txt"),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            InputSource::resolve(Some("other.txt".to_string()), "input.txt"),
+            InputSource::Path("other.txt".into())
+        );
+    }
+
+    #[test]
+    pub fn test_read_lines() {
+        let res = InputSource::Path(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml").into())
+            .read_lines()
+            .unwrap();
+
+        assert_eq!(res.first().unwrap(), "[package]");
+    }
+
+    #[test]
+    pub fn test_read_missing_file() {
+        let res = InputSource::Path("does/not/exist.txt".into()).read_lines();
+
+        assert!(matches!(res, Err(InputError::NotFound(_))));
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "input file `does/not/exist.txt` not found (pass a path, `-` for stdin or set AOC_INPUT)"
+        );
+    }
+}
@@ -0,0 +1,14 @@
+mod answer;
+mod error;
+mod generate;
+mod grid;
+mod input;
+pub mod parse;
+mod solution;
+
+pub use answer::*;
+pub use error::*;
+pub use generate::*;
+pub use grid::*;
+pub use input::*;
+pub use solution::*;
@@ -0,0 +1,232 @@
+//! Combinators for the building blocks most puzzle inputs are made of. All of
+//! them report errors through [`ErrorTree`], so [`finish`] can turn failures
+//! into a [`ParseError`] pointing into the input.
+
+use std::{any, str::FromStr};
+
+use nom::{
+    bytes::complete::take_while1,
+    character::complete::{char, digit1, line_ending, multispace0, one_of, space0, space1},
+    combinator::{all_consuming, opt, recognize},
+    error::{context, ErrorKind, FromExternalError},
+    multi::separated_list1,
+    sequence::{pair, preceded, terminated, tuple},
+    Parser,
+};
+use nom_supreme::tag::complete::tag;
+use thiserror::Error;
+
+use crate::{ErrorTree, IResult, ParseError};
+
+#[derive(Debug, Error)]
+#[error("number fitting into `{0}`")]
+struct OutOfRange(&'static str);
+
+/// Converts the recognized digits. A number that does not fit into `T`
+/// fails the whole parser instead of letting an enclosing list end early.
+fn parse_number<'a, T: FromStr>(
+    digits: impl Parser<&'a str, &'a str, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, T> {
+    let mut digits = digits;
+
+    move |input| {
+        let (rest, number) = digits.parse(input)?;
+
+        match number.parse::<T>() {
+            Ok(n) => Ok((rest, n)),
+            Err(_) => Err(nom::Err::Failure(ErrorTree::from_external_error(
+                number,
+                ErrorKind::MapRes,
+                OutOfRange(any::type_name::<T>()),
+            ))),
+        }
+    }
+}
+
+/// An unsigned integer. Numbers that do not fit into `T` are an error
+/// instead of being truncated.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    parse_number(context("number", digit1))(input)
+}
+
+/// An integer with an optional `+` or `-` sign.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    parse_number(recognize(pair(
+        opt(one_of("+-")),
+        context("number", digit1),
+    )))(input)
+}
+
+/// One or more items separated by spaces or tabs.
+pub fn spaced<'a, O>(
+    item: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(space1, item)
+}
+
+/// One or more items separated by commas, optionally followed by spaces.
+pub fn comma_separated<'a, O>(
+    item: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// Like `separated_list1`, but once a separator is followed by more text
+/// the item has to parse. This reports a malformed line where it breaks
+/// instead of silently ending the list before it. A separator followed by a
+/// blank line or the end of input ends the list.
+fn separated_lines<'a, O, S>(
+    mut separator: impl Parser<&'a str, S, ErrorTree<&'a str>>,
+    mut item: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    move |input| {
+        let (mut input, first) = item.parse(input)?;
+        let mut output = vec![first];
+
+        while let Ok((rest, _)) = separator.parse(input) {
+            if rest.is_empty() || rest.starts_with(['\n', '\r']) {
+                break;
+            }
+
+            let (rest, o) = item.parse(rest).map_err(|e| match e {
+                nom::Err::Error(e) => nom::Err::Failure(e),
+                e => e,
+            })?;
+
+            output.push(o);
+            input = rest;
+        }
+
+        Ok((input, output))
+    }
+}
+
+/// One item per line.
+pub fn lines<'a, O>(
+    item: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_lines(line_ending, item)
+}
+
+/// Blocks separated by a blank line.
+pub fn blocks<'a, O>(
+    block: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_lines(pair(line_ending, line_ending), block)
+}
+
+/// A `key: value` header, returning the value.
+pub fn key_value<'a, O>(
+    key: &'static str,
+    value: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(tuple((tag(key), char(':'), space0)), value)
+}
+
+/// A section starting with a `label:` line, like `seed-to-soil map:`,
+/// returning the label together with the parsed body.
+pub fn section<'a, O>(
+    body: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (&'a str, O)> {
+    pair(
+        terminated(
+            context(
+                "section label",
+                take_while1(|c: char| c != ':' && c != '\n' && c != '\r'),
+            ),
+            pair(char(':'), line_ending),
+        ),
+        body,
+    )
+}
+
+/// Runs `parser` on the whole `source`, allowing trailing whitespace, and
+/// reports failures as a [`ParseError`] into `source`.
+pub fn finish<'a, O>(
+    source: &'a str,
+    parser: impl Parser<&'a str, O, ErrorTree<&'a str>>,
+) -> Result<O, ParseError> {
+    all_consuming(terminated(parser, multispace0))(source)
+        .map(|(_, o)| o)
+        .map_err(|e| ParseError::from_nom(source, e))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parse::*;
+    use crate::*;
+
+    #[test]
+    pub fn test_numbers() {
+        assert_eq!(unsigned::<u64>("123 4").unwrap(), (" 4", 123));
+        assert_eq!(signed::<i64>("-17,").unwrap(), (",", -17));
+        assert_eq!(signed::<i64>("+5").unwrap(), ("", 5));
+
+        assert_eq!(
+            finish("1234", unsigned::<u8>),
+            Err(ParseError::new(1, 1, "number fitting into `u8`"))
+        );
+        assert_eq!(
+            finish("-x", signed::<i32>),
+            Err(ParseError::new(1, 2, "number"))
+        );
+    }
+
+    #[test]
+    pub fn test_lists() {
+        assert_eq!(
+            finish("1  2\t3\n", spaced(unsigned::<u32>)),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            finish("1,2, -3", comma_separated(signed::<i32>)),
+            Ok(vec![1, 2, -3])
+        );
+        assert_eq!(
+            finish("1 2\n3\n\n4 5", blocks(lines(spaced(unsigned::<u8>)))),
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4, 5]]])
+        );
+        assert_eq!(
+            finish("1 2\n3 x", lines(spaced(unsigned::<u8>))),
+            Err(ParseError::new(2, 3, "end of input"))
+        );
+        assert_eq!(
+            finish("1 2\nx 3", lines(spaced(unsigned::<u8>))),
+            Err(ParseError::new(2, 1, "number"))
+        );
+        assert_eq!(
+            finish("1\n2\n\n3\n\n", blocks(lines(unsigned::<u8>))),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    pub fn test_key_value() {
+        assert_eq!(
+            finish("seeds: 79 14", key_value("seeds", spaced(unsigned::<u64>))),
+            Ok(vec![79, 14])
+        );
+        assert_eq!(
+            finish("seed: 79 14", key_value("seeds", spaced(unsigned::<u64>))),
+            Err(ParseError::new(1, 1, "`seeds`"))
+        );
+    }
+
+    #[test]
+    pub fn test_section() {
+        assert_eq!(
+            finish(
+                "seed-to-soil map:\n50 98 2\n52 50 48\n",
+                section(lines(spaced(unsigned::<u64>)))
+            ),
+            Ok(("seed-to-soil map", vec![vec![50, 98, 2], vec![52, 50, 48]]))
+        );
+        assert_eq!(
+            finish(
+                "seed-to-soil map\n50 98 2",
+                section(lines(spaced(unsigned::<u64>)))
+            ),
+            Err(ParseError::new(1, 17, "`:`"))
+        );
+    }
+}
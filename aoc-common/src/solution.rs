@@ -0,0 +1,20 @@
+use std::error::Error;
+
+use crate::{Answer, ParseError};
+
+/// Why a part has no answer for input that parsed fine, like a network the
+/// ghosts never get through. Kept apart from the answer so runners report it
+/// instead of taking its message for one.
+pub type SolveError = Box<dyn Error + Send + Sync>;
+
+/// A puzzle solution split into parsing the input and solving both parts on
+/// the parsed representation.
+pub trait Solution {
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError>;
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError>;
+}
@@ -0,0 +1,145 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub expected: String,
+}
+
+/// The known correct answers, stored in `answers.toml` at the workspace root.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        };
+
+        f.pad(status)
+    }
+}
+
+impl Answers {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("aoc is a workspace member")
+            .join("answers.toml")
+    }
+
+    /// Loads the registry from `path`. A missing file is an empty registry.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("cannot parse `{}`", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("cannot read `{}`", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("cannot write `{}`", path.display()))
+    }
+
+    pub fn find(&self, day: u8, part: u8, input: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.part == part && e.input == input)
+    }
+
+    pub fn check(&self, day: u8, part: u8, input: &str, answer: &str) -> Status {
+        match self.find(day, part, input) {
+            Some(e) if e.expected == answer => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Missing,
+        }
+    }
+
+    /// Adds an entry, keeping the registry sorted by day and part.
+    pub fn record(&mut self, entry: Entry) {
+        self.entries.push(entry);
+        self.entries.sort_by_key(|e| (e.day, e.part));
+    }
+}
+
+/// Hashes the puzzle input with 64 bit FNV-1a, which stays stable across
+/// Rust versions and platforms.
+pub fn hash_input(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325_u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::answers::*;
+
+    #[test]
+    pub fn test_hash_input() {
+        assert_eq!(hash_input(""), "cbf29ce484222325");
+        assert_eq!(hash_input("a"), "af63dc4c8601ec8c");
+        assert_ne!(hash_input("1abc2\n"), hash_input("1abc2"));
+    }
+
+    #[test]
+    pub fn test_check() {
+        let mut answers = Answers::default();
+        answers.record(Entry {
+            day: 1,
+            part: 2,
+            input: "abc".to_string(),
+            expected: "281".to_string(),
+        });
+
+        assert_eq!(answers.check(1, 2, "abc", "281"), Status::Pass);
+        assert_eq!(answers.check(1, 2, "abc", "280"), Status::Fail);
+        assert_eq!(answers.check(1, 2, "def", "281"), Status::Missing);
+        assert_eq!(answers.check(1, 1, "abc", "281"), Status::Missing);
+    }
+
+    #[test]
+    pub fn test_roundtrip() {
+        let mut answers = Answers::default();
+        answers.record(Entry {
+            day: 2,
+            part: 1,
+            input: "abc".to_string(),
+            expected: "8".to_string(),
+        });
+        answers.record(Entry {
+            day: 1,
+            part: 1,
+            input: "def".to_string(),
+            expected: "142".to_string(),
+        });
+
+        let content = toml::to_string(&answers).unwrap();
+
+        assert!(content.starts_with("[[answer]]\nday = 1\n"));
+        assert_eq!(toml::from_str::<Answers>(&content).unwrap(), answers);
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{Answer, ParseError, Solution, SolveError};
+
+pub struct Solved {
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Why a day has no answer. Neither is ever taken for one.
+#[derive(Debug)]
+pub enum Failure {
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+pub struct Day {
+    pub number: u8,
+    pub solve: fn(&str, u8) -> Result<Solved, Failure>,
+}
+
+impl Day {
+    pub fn default_input(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(format!("day-{:02}", self.number))
+            .join("input.txt")
+    }
+}
+
+fn solve<S: Solution>(input: &str, part: u8) -> Result<Solved, Failure> {
+    let start = Instant::now();
+    let parsed = S::parse(input).map_err(Failure::Parse)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = match part {
+        1 => S::part_one(&parsed),
+        _ => S::part_two(&parsed),
+    }
+    .map_err(Failure::Solve)?;
+    let solve = start.elapsed();
+
+    Ok(Solved {
+        answer,
+        parse,
+        solve,
+    })
+}
+
+macro_rules! day {
+    ($number:literal, $solution:ty) => {
+        Day {
+            number: $number,
+            solve: solve::<$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(1, day_01::Day01),
+    day!(2, day_02::Day02),
+    day!(3, day_03::Day03),
+    day!(4, day_04::Day04),
+    day!(5, day_05::Day05),
+    day!(6, day_06::Day06),
+    day!(7, day_07::Day07),
+    day!(8, day_08::Day08),
+    day!(9, day_09::Day09),
+    day!(10, day_10::Day10),
+    day!(11, day_11::Day11),
+    day!(12, day_12::Day12),
+    day!(13, day_13::Day13),
+    day!(15, day_15::Day15),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
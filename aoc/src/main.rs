@@ -0,0 +1,322 @@
+mod answers;
+mod days;
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::InputSource;
+use clap::{Parser, Subcommand};
+use tracing_subscriber::EnvFilter;
+
+use answers::{hash_input, Answers, Entry, Status};
+use days::{Day, Failure, DAYS};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solutions of one day or of every day
+    Run {
+        #[arg(required_unless_present = "all")]
+        day: Option<u8>,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        #[arg(long, conflicts_with_all = ["day", "input"])]
+        all: bool,
+
+        /// Input file, `-` for stdin. Defaults to $AOC_INPUT or the input.txt of the day
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Check the solutions against the answers registered in answers.toml
+    Verify {
+        /// Only verify this day
+        day: Option<u8>,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2), requires = "day")]
+        part: Option<u8>,
+
+        /// Register answers that are missing instead of only reporting them
+        #[arg(long)]
+        record: bool,
+
+        /// Answers registry. Defaults to answers.toml in the workspace root
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+}
+
+struct Row {
+    day: u8,
+    part: u8,
+    answer: String,
+    parse: Duration,
+    solve: Duration,
+}
+
+fn run_day(day: &Day, parts: &[u8], input: &str) -> Result<Vec<Row>> {
+    parts
+        .iter()
+        .map(|part| {
+            let solved = (day.solve)(input, *part).map_err(|e| match e {
+                Failure::Parse(e) => anyhow!(e.render(input))
+                    .context(format!("cannot parse input of day {}", day.number)),
+                Failure::Solve(e) => {
+                    anyhow!(e).context(format!("cannot solve day {} part {part}", day.number))
+                }
+            })?;
+
+            Ok(Row {
+                day: day.number,
+                part: *part,
+                answer: solved.answer.to_string(),
+                parse: solved.parse,
+                solve: solved.solve,
+            })
+        })
+        .collect()
+}
+
+fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+
+    match nanos {
+        0..=999 => format!("{nanos} ns"),
+        1_000..=999_999 => format!("{:.2} µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2} ms", nanos as f64 / 1e6),
+        _ => format!("{:.2} s", nanos as f64 / 1e9),
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let answer_width = rows
+        .iter()
+        .map(|r| r.answer.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("answer".len());
+
+    let separator = format!(
+        "{:-<3}-+-{:-<4}-+-{:-<answer_width$}-+-{:-<10}-+-{:-<10}",
+        "", "", "", "", ""
+    );
+
+    println!(
+        "{:>3} | {:>4} | {:<answer_width$} | {:>10} | {:>10}",
+        "day", "part", "answer", "parse", "solve"
+    );
+    println!("{separator}");
+
+    for row in rows {
+        println!(
+            "{:>3} | {:>4} | {:<answer_width$} | {:>10} | {:>10}",
+            row.day,
+            row.part,
+            row.answer,
+            format_duration(row.parse),
+            format_duration(row.solve)
+        );
+    }
+
+    if rows.len() > 1 {
+        let parse = rows.iter().map(|r| r.parse).sum::<Duration>();
+        let solve = rows.iter().map(|r| r.solve).sum::<Duration>();
+
+        println!("{separator}");
+        println!(
+            "{:>3}   {:>4}   {:<answer_width$} | {:>10} | {:>10}",
+            "",
+            "",
+            "total",
+            format_duration(parse),
+            format_duration(solve)
+        );
+    }
+}
+
+fn verify(days: &[&Day], parts: &[u8], path: &Path, record: bool) -> Result<()> {
+    let mut answers = Answers::load(path)?;
+    let mut failed = 0;
+    let mut recorded = 0;
+
+    println!(
+        "{:>3} | {:>4} | {:<8} | {:<20} | expected",
+        "day", "part", "status", "answer"
+    );
+    println!(
+        "{:-<3}-+-{:-<4}-+-{:-<8}-+-{:-<20}-+-{:-<20}",
+        "", "", "", "", ""
+    );
+
+    for day in days {
+        let input = InputSource::Path(day.default_input()).read_to_string()?;
+        let hash = hash_input(&input);
+
+        for row in run_day(day, parts, &input)? {
+            let status = answers.check(day.number, row.part, &hash, &row.answer);
+            let expected = answers
+                .find(day.number, row.part, &hash)
+                .map(|e| e.expected.clone())
+                .unwrap_or_default();
+
+            let label = match status {
+                Status::Fail => {
+                    failed += 1;
+                    status.to_string()
+                }
+                Status::Missing if record => {
+                    answers.record(Entry {
+                        day: day.number,
+                        part: row.part,
+                        input: hash.clone(),
+                        expected: row.answer.clone(),
+                    });
+                    recorded += 1;
+                    "recorded".to_string()
+                }
+                _ => status.to_string(),
+            };
+
+            println!(
+                "{:>3} | {:>4} | {:<8} | {:<20} | {}",
+                row.day, row.part, label, row.answer, expected
+            );
+        }
+    }
+
+    if recorded > 0 {
+        answers.save(path)?;
+        println!("\nrecorded {recorded} new answers in `{}`", path.display());
+    }
+
+    if failed > 0 {
+        bail!("{failed} answers do not match `{}`", path.display());
+    }
+
+    Ok(())
+}
+
+fn parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    }
+}
+
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            all,
+            input,
+        } => {
+            let parts = parts(part);
+
+            let mut rows = vec![];
+            if all {
+                for day in DAYS {
+                    let input = InputSource::Path(day.default_input()).read_to_string()?;
+                    rows.append(&mut run_day(day, &parts, &input)?);
+                }
+            } else {
+                let number = day.expect("required unless --all");
+                let Some(day) = days::find(number) else {
+                    bail!("day {number} is not solved yet");
+                };
+
+                let source = InputSource::resolve(
+                    input.map(|i| i.to_string_lossy().into_owned()),
+                    day.default_input(),
+                );
+                rows = run_day(day, &parts, &source.read_to_string()?)?;
+            }
+
+            print_table(&rows);
+        }
+        Command::Verify {
+            day,
+            part,
+            record,
+            answers,
+        } => {
+            let days: Vec<&Day> = match day {
+                Some(number) => match days::find(number) {
+                    Some(day) => vec![day],
+                    None => bail!("day {number} is not solved yet"),
+                },
+                None => DAYS.iter().collect(),
+            };
+
+            let path = answers.unwrap_or_else(Answers::default_path);
+            verify(&days, &parts(part), &path, record)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    pub fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512 ns");
+        assert_eq!(format_duration(Duration::from_nanos(47_140)), "47.14 µs");
+        assert_eq!(format_duration(Duration::from_micros(75_920)), "75.92 ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50 s");
+    }
+
+    #[test]
+    pub fn test_days_default_input() {
+        assert!(DAYS.iter().all(|d| d.default_input().exists()));
+        assert_eq!(days::find(14).map(|d| d.number), None);
+    }
+
+    #[test]
+    pub fn test_parse_error_snippet() {
+        let input = "0 3 6 9\n1 3 x 10";
+        let Err(Failure::Parse(err)) = (days::find(9).unwrap().solve)(input, 1) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(
+            err.render(input).lines().last(),
+            Some("  |     ^ expected integer")
+        );
+    }
+
+    #[test]
+    pub fn test_solve_error() {
+        let input = "0 3 6 9\n1 2 4 8";
+        let day = days::find(9).unwrap();
+
+        let Err(Failure::Solve(err)) = (day.solve)(input, 2) else {
+            panic!("expected a solve error");
+        };
+        assert_eq!(err.to_string(), "line 2 is not the values of a polynomial");
+
+        let err = run_day(day, &[1], input).err().unwrap();
+        assert_eq!(
+            format!("{err:#}"),
+            "cannot solve day 9 part 1: line 2 is not the values of a polynomial"
+        );
+    }
+}
@@ -1,9 +0,0 @@
-use {{crate_name}}::*;
-
-fn main() {
-    let lines = read_lines();
-
-    let res = part_two(lines);
-
-    println!("part 2 :: {}", res);
-}
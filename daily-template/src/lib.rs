@@ -1,21 +1,42 @@
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
+use aoc_common::{seeded_rng, Answer, Generator, ParseError, Solution, SolveError};
+use rand::Rng;
 
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
+pub fn part_one(inp: &[String]) -> String {
+    "".to_string()
+}
 
-    res
+pub fn part_two(inp: &[String]) -> String {
+    "".to_string()
 }
 
+pub struct {{project-name | upper_camel_case}};
 
-pub fn part_one(inp: Vec<String>) -> String {
-    "".to_string()
+impl Solution for {{project-name | upper_camel_case}} {
+    type Input<'a> = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input.lines().map(|l| l.to_string()).collect())
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
 }
 
-pub fn part_two(inp: Vec<String>) -> String {
-    "".to_string()
+impl Generator for {{project-name | upper_camel_case}} {
+    /// `size` lines of random puzzle input.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+
+        (0..size)
+            .map(|_| rng.gen_range(0..100).to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
 }
 
 #[cfg(test)]
@@ -28,7 +49,7 @@ mod test {
             "".to_string(),
         ];
 
-        let res = part_one(input);
+        let res = part_one(&input);
 
         assert_eq!(res, "");
     }
@@ -39,7 +60,7 @@ mod test {
             "".to_string(),
         ];
 
-        let res = part_two(input);
+        let res = part_two(&input);
 
         assert_eq!(res, "");
     }
@@ -1,20 +1,77 @@
-use day_01::{part_one, read_lines, part_two};
+use aoc_common::{default_input, Generator, InputSource, Solution};
+use day_01::Day01;
+use divan::{black_box, AllocProfiler, Bencher};
+
+#[global_allocator]
+static ALLOC: AllocProfiler = AllocProfiler::system();
 
 fn main() {
     divan::main();
 }
 
+fn input() -> String {
+    InputSource::from_env(default_input!())
+        .read_to_string()
+        .unwrap()
+}
+
+#[divan::bench]
+fn load() -> String {
+    input()
+}
+
+#[divan::bench]
+fn parse(bencher: Bencher) {
+    let input = input();
+
+    bencher.bench(|| Day01::parse(black_box(&input)).unwrap());
+}
+
 #[divan::bench]
-fn part_1() {
-    let lines = read_lines();
+fn part_1(bencher: Bencher) {
+    let input = input();
+    let parsed = Day01::parse(&input).unwrap();
 
-    part_one(lines);
+    bencher.bench(|| Day01::part_one(black_box(&parsed)));
 }
 
 #[divan::bench]
-fn part_2() {
-    let lines = read_lines();
+fn part_2(bencher: Bencher) {
+    let input = input();
+    let parsed = Day01::parse(&input).unwrap();
 
-    part_two(lines);
+    bencher.bench(|| Day01::part_two(black_box(&parsed)));
 }
 
+/// Parsing and both parts on generated input of growing size, to see how
+/// the solutions scale.
+#[divan::bench_group]
+mod generated {
+    use super::*;
+
+    const SEED: u64 = 2023;
+    const SIZES: &[usize] = &[100, 1_000, 10_000];
+
+    #[divan::bench(args = SIZES)]
+    fn parse(bencher: Bencher, size: usize) {
+        let input = Day01::generate(size, SEED);
+
+        bencher.bench(|| Day01::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn part_1(bencher: Bencher, size: usize) {
+        let input = Day01::generate(size, SEED);
+        let parsed = Day01::parse(&input).unwrap();
+
+        bencher.bench(|| Day01::part_one(black_box(&parsed)));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn part_2(bencher: Bencher, size: usize) {
+        let input = Day01::generate(size, SEED);
+        let parsed = Day01::parse(&input).unwrap();
+
+        bencher.bench(|| Day01::part_two(black_box(&parsed)));
+    }
+}
@@ -1,9 +0,0 @@
-use day_01::*;
-
-fn main() {
-    let lines = read_lines();
-
-    let sum = part_one(lines);
-
-    println!("part one :: {}", sum);
-}
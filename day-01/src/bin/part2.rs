@@ -1,9 +0,0 @@
-use day_01::*;
-
-fn main() {
-    let lines = read_lines();
-
-    let sum = part_two(lines);
-
-    println!("part two :: {}", sum);
-}
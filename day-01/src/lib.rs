@@ -1,15 +1,6 @@
+use aoc_common::{seeded_rng, Answer, Generator, ParseError, Solution, SolveError};
 use onig::Regex;
-
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
+use rand::Rng;
 
 pub fn find_first_number(inp: String) -> Option<u32> {
     inp.chars()
@@ -67,6 +58,10 @@ fn word_to_digit(inp: String) -> String {
     }
 }
 
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
 pub fn get_calibration_number(inp: String, with_words: bool) -> Option<u32> {
     let digits = get_numbers(inp, with_words).unwrap_or("".to_string());
 
@@ -79,13 +74,10 @@ pub fn get_calibration_number(inp: String, with_words: bool) -> Option<u32> {
 
     let combined = format!("{}{}", first_number.unwrap(), last_number.unwrap());
 
-    match combined.parse::<u32>() {
-        Ok(c) => Some(c),
-        Err(_) => None,
-    }
+    combined.parse::<u32>().ok()
 }
 
-pub fn part_one(lines: Vec<String>) -> u32 {
+pub fn part_one(lines: &[String]) -> u32 {
     lines
         .iter()
         .map(|l| get_calibration_number(l.to_string(), false))
@@ -96,7 +88,7 @@ pub fn part_one(lines: Vec<String>) -> u32 {
         })
 }
 
-pub fn part_two(lines: Vec<String>) -> u32 {
+pub fn part_two(lines: &[String]) -> u32 {
     let mut sum = 0;
     for line in lines.iter() {
         let calibration_number = get_calibration_number(line.to_string(), true);
@@ -113,6 +105,56 @@ pub fn part_two(lines: Vec<String>) -> u32 {
     sum
 }
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input.lines().map(|l| l.to_string()).collect())
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day01 {
+    /// `size` lines of letters mixed with digits and spelled out digits.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+        let mut lines = Vec::with_capacity(size);
+
+        for _ in 0..size {
+            let mut line = String::new();
+
+            for _ in 0..rng.gen_range(1..8) {
+                match rng.gen_range(0..3) {
+                    0 => line.push(char::from(rng.gen_range(b'1'..=b'9'))),
+                    1 => line.push_str(DIGIT_WORDS[rng.gen_range(0..9)]),
+                    _ => {
+                        for _ in 0..rng.gen_range(1..6) {
+                            line.push(char::from(rng.gen_range(b'a'..=b'z')));
+                        }
+                    }
+                }
+            }
+
+            // part one needs at least one digit on every line
+            let at = rng.gen_range(0..=line.len());
+            line.insert(at, char::from(rng.gen_range(b'1'..=b'9')));
+
+            lines.push(line);
+        }
+
+        lines.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
@@ -171,7 +213,19 @@ mod test {
             "7pqrstsixteen".to_string(),
         ]);
 
-        let res = part_two(input);
+        let res = part_two(&input);
         assert_eq!(res, 281);
     }
+
+    #[test]
+    fn test_generate() {
+        let input = Day01::generate(50, 1);
+        assert_eq!(input, Day01::generate(50, 1));
+
+        let lines = Day01::parse(&input).unwrap();
+        assert_eq!(lines.len(), 50);
+        assert!(lines
+            .iter()
+            .all(|l| get_calibration_number(l.to_string(), false).is_some()));
+    }
 }
@@ -1,16 +1,17 @@
-use anyhow::{bail, Result};
-use regex::Regex;
-
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
+use aoc_common::{
+    parse::{finish, lines, unsigned},
+    seeded_rng, Answer, Generator, IResult, ParseError, Solution, SolveError,
+};
+use nom::{
+    branch::alt,
+    character::complete::{char, space0, space1},
+    combinator::{cut, map, value},
+    error::context,
+    multi::separated_list1,
+    sequence::{delimited, pair, terminated, tuple},
+};
+use nom_supreme::tag::complete::tag;
+use rand::{seq::SliceRandom, Rng};
 
 pub struct Game {
     pub id: u32,
@@ -18,20 +19,8 @@ pub struct Game {
 }
 
 impl Game {
-    pub fn from_string(inp: String) -> Result<Self> {
-        let id = extract_game_id(inp.clone());
-        if id.is_none() {
-            bail!("cannot get game id");
-        }
-
-        Ok(Self {
-            id: id.unwrap(),
-            sets: sets_from_string(inp),
-        })
-    }
-
     pub fn minimal_set(&self) -> Set {
-        let mut output = Set::from_string("".to_string());
+        let mut output = Set::EMPTY;
 
         for set in &self.sets {
             if set.blue > output.blue {
@@ -67,13 +56,11 @@ pub struct Set {
 }
 
 impl Set {
-    pub fn from_string(inp: String) -> Self {
-        Self {
-            blue: extract_color_count(inp.clone(), "blue".to_string()),
-            green: extract_color_count(inp.clone(), "green".to_string()),
-            red: extract_color_count(inp, "red".to_string()),
-        }
-    }
+    pub const EMPTY: Set = Set {
+        blue: 0,
+        green: 0,
+        red: 0,
+    };
 
     pub fn power(&self) -> u32 {
         self.blue * self.green * self.red
@@ -84,39 +71,63 @@ impl Set {
     }
 }
 
-fn sets_from_string(inp: String) -> Vec<Set> {
-    let parts: Vec<&str> = inp.split(':').collect();
+/// A count of cubes of one color, like `3 blue`, as a set of only those.
+pub fn cubes(input: &str) -> IResult<&str, Set> {
+    let (input, count) = terminated(unsigned, space1)(input)?;
 
-    parts[1]
-        .split(';')
-        .map(|p| Set::from_string(p.to_string()))
-        .collect()
+    alt((
+        value(
+            Set {
+                blue: count,
+                ..Set::EMPTY
+            },
+            tag("blue"),
+        ),
+        value(
+            Set {
+                green: count,
+                ..Set::EMPTY
+            },
+            tag("green"),
+        ),
+        value(
+            Set {
+                red: count,
+                ..Set::EMPTY
+            },
+            tag("red"),
+        ),
+    ))(input)
 }
 
-fn extract_color_count(inp: String, color: String) -> u32 {
-    let pattern = Regex::new(format!("(\\d+) {}", color).as_str()).unwrap();
-
-    match pattern.captures(&inp) {
-        Some(cap) => (cap[1]).parse::<u32>().unwrap_or(0),
-        None => 0,
-    }
+/// The cubes shown at once, like `3 blue, 4 red`. Once a separator is read
+/// the next item has to parse, so errors point into the broken item.
+pub fn set(input: &str) -> IResult<&str, Set> {
+    map(
+        separated_list1(pair(char(','), space0), cut(cubes)),
+        |cubes| {
+            cubes.into_iter().fold(Set::EMPTY, |set, c| Set {
+                blue: set.blue + c.blue,
+                green: set.green + c.green,
+                red: set.red + c.red,
+            })
+        },
+    )(input)
 }
 
-fn extract_game_id(inp: String) -> Option<u32> {
-    let pattern = Regex::new("Game (\\d+):").unwrap();
-    let captures = pattern.captures(&inp).unwrap();
+pub fn game(input: &str) -> IResult<&str, Game> {
+    let (input, id) = context(
+        "`Game <id>:`",
+        delimited(tag("Game "), unsigned, tuple((char(':'), space0))),
+    )(input)?;
+    let (input, sets) = separated_list1(pair(char(';'), space0), cut(set))(input)?;
 
-    match (captures[1]).parse::<u32>() {
-        Ok(num) => Some(num),
-        Err(_) => None,
-    }
+    Ok((input, Game { id, sets }))
 }
 
-pub fn part_one(inp: Vec<String>, total: Set) -> u32 {
-    inp.iter()
-        .map(|l| Game::from_string(l.to_string()))
-        .filter(|g| !g.is_err())
-        .map(|g| g.unwrap())
+pub fn part_one(games: &[Game], total: Set) -> u32 {
+    games
+        .iter()
         .filter(|g| g.is_valid(total.clone()))
         .fold(0, |mut sum, g| {
             sum += g.id;
@@ -124,14 +135,65 @@ pub fn part_one(inp: Vec<String>, total: Set) -> u32 {
         })
 }
 
-pub fn part_two(inp: Vec<String>) -> u32 {
-    inp.iter()
-        .map(|l| Game::from_string(l.to_string()))
-        .filter(|g| !g.is_err())
-        .fold(0, |mut sum, g| {
-            sum += g.unwrap().minimal_set().power();
-            sum
-        })
+pub fn part_two(games: &[Game]) -> u32 {
+    games.iter().fold(0, |mut sum, g| {
+        sum += g.minimal_set().power();
+        sum
+    })
+}
+
+pub struct Day02;
+
+impl Day02 {
+    pub const TOTAL: Set = Set {
+        blue: 14,
+        green: 13,
+        red: 12,
+    };
+}
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        finish(input, lines(game))
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input, Self::TOTAL).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day02 {
+    /// `size` games of one to six sets, each drawing up to 20 cubes of some
+    /// of the colors.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+        let mut colors = ["red", "green", "blue"];
+        let mut games = Vec::with_capacity(size);
+
+        for id in 1..=size {
+            let sets = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    colors.shuffle(&mut rng);
+
+                    colors[..rng.gen_range(1..=3)]
+                        .iter()
+                        .map(|color| format!("{} {color}", rng.gen_range(1..=20)))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                })
+                .collect::<Vec<String>>();
+
+            games.push(format!("Game {id}: {}", sets.join("; ")));
+        }
+
+        games.join("\n")
+    }
 }
 
 #[cfg(test)]
@@ -148,16 +210,16 @@ mod test {
             "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".to_string(),
         ]);
 
-        let res = part_two(input);
+        let games = Day02::parse(&input.join("\n")).unwrap();
+
+        let res = part_two(&games);
 
         assert_eq!(res, 2286);
     }
 
     #[test]
     pub fn test_minimal_set() {
-        let game =
-            Game::from_string("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string())
-                .unwrap();
+        let (_, game) = game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
 
         let res = game.minimal_set();
         assert_eq!(
@@ -172,8 +234,8 @@ mod test {
 
     #[test]
     pub fn test_set_power() {
-        let power = Set::from_string("6 blue, 2 green, 4 red".to_string()).power();
-        assert_eq!(power, 48);
+        let (_, set) = set("6 blue, 2 green, 4 red").unwrap();
+        assert_eq!(set.power(), 48);
     }
 
     #[test]
@@ -192,18 +254,16 @@ mod test {
             red: 12,
         };
 
-        let res = part_one(input, total_set);
+        let games = Day02::parse(&input.join("\n")).unwrap();
+
+        let res = part_one(&games, total_set);
 
         assert_eq!(res, 8);
     }
 
     #[test]
     pub fn test_game_is_valid() {
-        let game =
-            Game::from_string("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string());
-
-        assert!(game.is_ok());
-        let game = game.unwrap();
+        let (_, game) = game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
 
         let res = game.is_valid(Set {
             blue: 6,
@@ -222,7 +282,7 @@ mod test {
 
     #[test]
     pub fn test_set_is_valid() {
-        let set = Set::from_string("3 blue, 4 red".to_string());
+        let (_, set) = set("3 blue, 4 red").unwrap();
         let res = set.is_valid(Set {
             blue: 6,
             green: 5,
@@ -241,67 +301,54 @@ mod test {
     }
 
     #[test]
-    pub fn test_sets_from_string() {
-        let res =
-            sets_from_string("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string());
-
+    pub fn test_cubes() {
         assert_eq!(
-            res,
-            Vec::from([
+            cubes("3 blue, 4 red").unwrap(),
+            (
+                ", 4 red",
                 Set {
                     blue: 3,
                     green: 0,
-                    red: 4
-                },
-                Set {
-                    blue: 6,
-                    green: 2,
-                    red: 1
-                },
-                Set {
-                    blue: 0,
-                    green: 2,
                     red: 0
-                },
-            ])
+                }
+            )
         );
+        assert_eq!(cubes("4 red").unwrap().1.red, 4);
+        assert!(cubes("4 purple").is_err());
     }
 
     #[test]
-    pub fn test_set_from_string() {
-        let res = Set::from_string("3 blue, 4 red".to_string());
+    pub fn test_set() {
+        let (rest, res) = set("3 blue, 4 red; 1 red").unwrap();
 
+        assert_eq!(rest, "; 1 red");
         assert_eq!(res.blue, 3);
         assert_eq!(res.green, 0);
         assert_eq!(res.red, 4);
     }
 
     #[test]
-    pub fn test_extract_color_count() {
-        let res = extract_color_count("3 blue, 4 red".to_string(), "blue".to_string());
-        assert_eq!(res, 3);
+    pub fn test_parse_error() {
+        let res = Day02::parse("Game 1: 3 blue\nGme 2: 4 red");
+        assert_eq!(res.err(), Some(ParseError::new(2, 1, "`Game <id>:`")));
 
-        let res = extract_color_count("3 blue, 4 red".to_string(), "red".to_string());
-        assert_eq!(res, 4);
-    }
+        let res = Day02::parse("Game x: 3 blue");
+        assert_eq!(res.err(), Some(ParseError::new(1, 6, "number")));
 
-    #[test]
-    pub fn test_extract_game_id() {
-        let res =
-            extract_game_id("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string());
+        let res = Day02::parse("Game 1: 3 blue; 2 red, 3 purple");
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(1, 26, "`blue` or `green` or `red`"))
+        );
 
-        assert!(res.is_some());
-        assert_eq!(res.unwrap(), 1)
+        let res = Day02::parse("Game 1: 3 blue\nGame 2: 1 red, x red");
+        assert_eq!(res.err(), Some(ParseError::new(2, 16, "number")));
     }
 
     #[test]
-    pub fn test_from_string() {
-        let res =
-            Game::from_string("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string());
-
-        assert!(res.is_ok());
+    pub fn test_game() {
+        let (_, res) = game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
 
-        let res = res.unwrap();
         assert_eq!(res.id, 1);
         assert_eq!(
             res.sets,
@@ -324,4 +371,14 @@ mod test {
             ])
         )
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day02::generate(50, 1);
+        assert_eq!(input, Day02::generate(50, 1));
+
+        let games = Day02::parse(&input).unwrap();
+        assert_eq!(games.len(), 50);
+        assert!(games.iter().all(|g| !g.sets.is_empty()));
+    }
 }
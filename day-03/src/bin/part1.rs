@@ -1,9 +0,0 @@
-use day_03::*;
-
-fn main() {
-    let lines = read_lines();
-
-    let res = part_one(lines);
-
-    println!("part 1 :: {}", res);
-}
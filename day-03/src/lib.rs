@@ -1,92 +1,55 @@
-use std::ops::Range;
-
-use regex::Regex;
-
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
-
-fn is_adjacent(range: Range<usize>, part_no: PartNumber) -> bool {
-    let min_end = range.end.saturating_sub(2);
-
-    range.start + 1 >= part_no.position.start && min_end <= part_no.position.end
-}
-
-fn is_neighboured_line(line_no: usize, p_no: PartNumber) -> bool {
-    p_no.position.line >= line_no.saturating_sub(1) && p_no.position.line <= line_no + 1
-}
+use aoc_common::{seeded_rng, Answer, Generator, Grid, ParseError, Point, Solution, SolveError};
+use rand::Rng;
 
 #[derive(Debug, PartialEq)]
 pub struct Engine {
-    lines: Vec<String>,
+    schematic: Grid<char>,
     part_nos: Vec<PartNumber>,
 }
 
 impl Engine {
-    pub fn from_lines(lines: Vec<String>) -> Self {
+    pub fn new(schematic: Grid<char>) -> Self {
         Self {
-            lines: lines.clone(),
-            part_nos: find_part_numbers(lines),
+            part_nos: find_part_numbers(&schematic),
+            schematic,
         }
     }
 
-    pub fn find_adj_part_nos(&self) -> Vec<u32> {
-        let mut output = Vec::new();
-
-        let pattern = Regex::new("([^\\.0-9])").unwrap();
-        for (line_no, line) in self.lines.iter().enumerate() {
-            for mat in pattern.find_iter(line) {
-                let mut adj_part_nos: Vec<u32> = self
-                    .part_nos
-                    .clone()
-                    .into_iter()
-                    .filter(|p_no| is_neighboured_line(line_no, *p_no))
-                    .filter(|p_no| is_adjacent(mat.range(), *p_no))
-                    .map(|p_no| p_no.number)
-                    .collect();
-
-                output.append(&mut adj_part_nos);
-            }
-        }
+    fn adjacent_part_nos(&self, symbol: Point) -> impl Iterator<Item = &PartNumber> {
+        self.part_nos.iter().filter(move |p_no| {
+            self.schematic
+                .neighbours_diagonal(symbol)
+                .any(|n| p_no.position.contains(n))
+        })
+    }
 
-        output
+    pub fn find_adj_part_nos(&self) -> Vec<u32> {
+        self.schematic
+            .iter()
+            .filter(|(_, c)| **c != '.' && !c.is_ascii_digit())
+            .flat_map(|(symbol, _)| self.adjacent_part_nos(symbol).map(|p_no| p_no.number))
+            .collect()
     }
 
     pub fn find_gear_ratios(&self) -> Vec<u32> {
-        let mut output = Vec::new();
-
-        let pattern = Regex::new("(\\*)").unwrap();
-        for (line_no, line) in self.lines.iter().enumerate() {
-            for mat in pattern.find_iter(line) {
-                let gear_part_nos: Vec<PartNumber> = self
-                    .part_nos
-                    .clone()
-                    .into_iter()
-                    .filter(|p_no| is_neighboured_line(line_no, *p_no))
-                    .filter(|p_no| is_adjacent(mat.range(), *p_no))
-                    .collect();
+        self.schematic
+            .iter()
+            .filter(|(_, c)| **c == '*')
+            .filter_map(|(symbol, _)| {
+                let gear_part_nos: Vec<&PartNumber> = self.adjacent_part_nos(symbol).collect();
 
                 if gear_part_nos.len() != 2 {
-                    continue;
+                    return None;
                 }
 
-                output.push(
+                Some(
                     gear_part_nos
                         .iter()
                         .map(|p_no| p_no.number)
                         .product::<u32>(),
-                );
-            }
-        }
-
-        output
+                )
+            })
+            .collect()
     }
 }
 
@@ -103,45 +66,103 @@ pub struct Position {
     pub end: usize,
 }
 
-pub fn find_part_numbers(lines: Vec<String>) -> Vec<PartNumber> {
+impl Position {
+    pub fn contains(&self, p: Point) -> bool {
+        p.y == self.line && (self.start..=self.end).contains(&p.x)
+    }
+}
+
+pub fn find_part_numbers(schematic: &Grid<char>) -> Vec<PartNumber> {
     let mut output: Vec<PartNumber> = Vec::new();
-    let pattern = Regex::new("([0-9]+)").unwrap();
 
-    for (line_counter, line) in lines.iter().enumerate() {
-        let mut mats: Vec<PartNumber> = pattern
-            .find_iter(line)
-            .map(|mat| PartNumber {
-                number: mat.as_str().to_string().parse::<u32>().unwrap(),
+    for (line, row) in schematic.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let digits = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+
+            if digits == 0 {
+                x += 1;
+                continue;
+            }
+
+            output.push(PartNumber {
+                number: row[x..x + digits]
+                    .iter()
+                    .fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap()),
                 position: Position {
-                    line: line_counter,
-                    start: mat.start(),
-                    end: mat.end() - 1,
+                    line,
+                    start: x,
+                    end: x + digits - 1,
                 },
-            })
-            .collect();
+            });
 
-        output.append(&mut mats);
+            x += digits;
+        }
     }
 
     output
 }
 
-pub fn part_one(inp: Vec<String>) -> u32 {
-    let engine = Engine::from_lines(inp);
+pub fn part_one(engine: &Engine) -> u32 {
+    engine.find_adj_part_nos().iter().sum::<u32>()
+}
 
-    engine
-        .find_adj_part_nos()
-        .iter()
-        .sum::<u32>()
+pub fn part_two(engine: &Engine) -> u32 {
+    engine.find_gear_ratios().iter().sum::<u32>()
 }
 
-pub fn part_two(inp: Vec<String>) -> u32 {
-    let engine = Engine::from_lines(inp);
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Engine;
 
-    engine
-        .find_gear_ratios()
-        .iter()
-        .sum::<u32>()
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(Engine::new(input.parse()?))
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day03 {
+    /// A `size` by `size` schematic with numbers of up to three digits and
+    /// symbols scattered between the dots, about as dense as the real input.
+    fn generate(size: usize, seed: u64) -> String {
+        const SYMBOLS: &[u8] = b"**#+$/@=%&-";
+
+        let mut rng = seeded_rng(seed);
+        let mut rows = Vec::with_capacity(size);
+
+        for _ in 0..size {
+            let mut row = String::with_capacity(size);
+
+            while row.len() < size {
+                let digits: u32 = rng.gen_range(1..=3);
+
+                match rng.gen_range(0..100) {
+                    0..=11 if row.len() + digits as usize <= size => {
+                        row.push_str(&rng.gen_range(1..10u32.pow(digits)).to_string());
+
+                        // keep the next number from running into this one
+                        if row.len() < size {
+                            row.push('.');
+                        }
+                    }
+                    12..=17 => row.push(char::from(SYMBOLS[rng.gen_range(0..SYMBOLS.len())])),
+                    _ => row.push('.'),
+                }
+            }
+
+            rows.push(row);
+        }
+
+        rows.join("\n")
+    }
 }
 
 #[cfg(test)]
@@ -150,7 +171,7 @@ mod test {
 
     #[test]
     pub fn test_find_adj_part_nos() {
-        let engine = Engine::from_lines(vec!["467..114..".to_string(), "...*......".to_string()]);
+        let engine = Engine::new("467..114..\n...*......".parse().unwrap());
 
         let res = engine.find_adj_part_nos();
 
@@ -159,7 +180,7 @@ mod test {
 
     #[test]
     pub fn test_find_part_numbers() {
-        let res = find_part_numbers(vec!["467..114..".to_string(), "...*......".to_string()]);
+        let res = find_part_numbers(&"467..114..\n...*......".parse().unwrap());
 
         let expected = vec![
             PartNumber {
@@ -185,10 +206,10 @@ mod test {
 
     #[test]
     pub fn test_engine_from_lines() {
-        let res = Engine::from_lines(vec!["467..114..".to_string(), "...*......".to_string()]);
+        let res = Engine::new("467..114..\n...*......".parse().unwrap());
 
         let expected = Engine {
-            lines: vec!["467..114..".to_string(), "...*......".to_string()],
+            schematic: "467..114..\n...*......".parse().unwrap(),
             part_nos: vec![
                 PartNumber {
                     number: 467,
@@ -214,18 +235,22 @@ mod test {
 
     #[test]
     pub fn test_find_part_numbers_with_gears() {
-        let engine = Engine::from_lines(vec![
-            "467..114..".to_string(),
-            "...*......".to_string(),
-            "..35..633.".to_string(),
-            "......#...".to_string(),
-            "617*......".to_string(),
-            ".....+.58.".to_string(),
-            "..592.....".to_string(),
-            "......755.".to_string(),
-            "...$.*....".to_string(),
-            ".664.598..".to_string(),
-        ]);
+        let engine = Day03::parse(
+            &[
+                "467..114..".to_string(),
+                "...*......".to_string(),
+                "..35..633.".to_string(),
+                "......#...".to_string(),
+                "617*......".to_string(),
+                ".....+.58.".to_string(),
+                "..592.....".to_string(),
+                "......755.".to_string(),
+                "...$.*....".to_string(),
+                ".664.598..".to_string(),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
 
         let res = engine.find_gear_ratios();
 
@@ -234,7 +259,7 @@ mod test {
 
     #[test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             "467..114..".to_string(),
             "...*......".to_string(),
             "..35..633.".to_string(),
@@ -247,14 +272,14 @@ mod test {
             ".664.598..".to_string(),
         ];
 
-        let res = part_one(input);
+        let res = part_one(&Day03::parse(&input.join("\n")).unwrap());
 
         assert_eq!(res, 4361);
     }
 
     #[test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "467..114..".to_string(),
             "...*......".to_string(),
             "..35..633.".to_string(),
@@ -267,8 +292,19 @@ mod test {
             ".664.598..".to_string(),
         ];
 
-        let res = part_two(input);
+        let res = part_two(&Day03::parse(&input.join("\n")).unwrap());
 
         assert_eq!(res, 467835);
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day03::generate(40, 1);
+        assert_eq!(input, Day03::generate(40, 1));
+
+        let engine = Day03::parse(&input).unwrap();
+        assert_eq!(engine.schematic.width(), 40);
+        assert_eq!(engine.schematic.height(), 40);
+        assert!(!engine.find_adj_part_nos().is_empty());
+    }
 }
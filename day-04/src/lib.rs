@@ -1,21 +1,14 @@
+use aoc_common::{
+    parse::{finish, lines, spaced, unsigned},
+    seeded_rng, Answer, Generator, IResult, ParseError, Solution, SolveError,
+};
 use nom::{
-    branch::alt,
-    bytes::complete::{tag, take_till, take_while_m_n},
-    combinator::{eof, iterator, map_res, recognize},
-    sequence::{delimited, terminated},
-    IResult,
+    bytes::complete::tag,
+    character::complete::{char, space1},
+    error::context,
+    sequence::{preceded, tuple},
 };
-
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
+use rand::{seq::SliceRandom, Rng};
 
 #[derive(Debug, PartialEq)]
 pub struct Card {
@@ -40,35 +33,17 @@ impl Card {
     }
 }
 
-pub fn from_str(input: &str) -> Result<u8, std::num::ParseIntError> {
-    input.parse::<u8>()
-}
-
-pub fn is_digit(input: char) -> bool {
-    input.is_ascii_digit()
-}
-
-pub fn parse_digit(input: &str) -> IResult<&str, u8> {
-    let (input, _) = take_till(|c: char| c.is_ascii_digit())(input)?;
-    map_res(take_while_m_n(1, 3, is_digit), from_str)(input)
-}
-
-pub fn parse_numbers(input: &str) -> IResult<&str, Vec<u8>> {
-    let (input, res) = take_till(|c: char| !c.is_ascii_digit() && c != ' ')(input)?;
-
-    let it: Vec<u8> = iterator(
-        res,
-        terminated(parse_digit, alt((tag(" "), recognize(eof)))),
-    )
-    .collect();
-
-    Ok((input, it))
-}
-
 pub fn card(line: &str) -> IResult<&str, Card> {
-    let (input, _) = delimited(tag("Card "), parse_digit, tag(":"))(line)?;
-    let (input, winning_no) = delimited(tag(" "), parse_numbers, tag("|"))(input)?;
-    let (input, numbers) = parse_numbers(input)?;
+    let (input, _) = context(
+        "`Card <id>:`",
+        tuple((tag("Card"), space1, unsigned::<u32>, char(':'))),
+    )(line)?;
+    let (input, winning_no) = preceded(space1, spaced(unsigned))(input)?;
+    let (input, _) = context(
+        "numbers separated by `|`",
+        tuple((space1, char('|'), space1)),
+    )(input)?;
+    let (input, numbers) = spaced(unsigned)(input)?;
 
     Ok((
         input,
@@ -79,20 +54,12 @@ pub fn card(line: &str) -> IResult<&str, Card> {
     ))
 }
 
-pub fn part_one(inp: Vec<String>) -> u32 {
-    inp.iter()
-        .map(|l| {
-            let (_, c) = card(l).unwrap();
-            c.points()
-        })
-        .sum()
+pub fn part_one(cards: &[Card]) -> u32 {
+    cards.iter().map(|c| c.points()).sum()
 }
 
-pub fn part_two(inp: Vec<String>) -> u32 {
-    let cards = inp.iter().map(|l| {
-        let (_, c) = card(l).unwrap();
-        c.wins() as u32
-    });
+pub fn part_two(cards: &[Card]) -> u32 {
+    let cards = cards.iter().map(|c| c.wins() as u32);
     let mut multiplier: Vec<usize> = vec![];
 
     let mut output = 0;
@@ -120,6 +87,62 @@ pub fn part_two(inp: Vec<String>) -> u32 {
     output
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        finish(input, lines(card))
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day04 {
+    /// `size` cards with ten winning numbers and 25 numbers each. Most cards
+    /// win nothing, so the copies in part two stay bounded.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+        let mut pool: Vec<u8> = (1..100).collect();
+        let mut cards = Vec::with_capacity(size);
+
+        let format = |numbers: &[u8]| {
+            numbers
+                .iter()
+                .map(|n| format!("{n:>2}"))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        for id in 1..=size {
+            pool.shuffle(&mut rng);
+
+            let wins = match rng.gen_range(0..10) {
+                0..=6 => 0,
+                _ => rng.gen_range(1..=4),
+            };
+            let (winning_no, rest) = pool.split_at(10);
+            let mut numbers = [&winning_no[..wins], &rest[..25 - wins]].concat();
+            numbers.shuffle(&mut rng);
+
+            cards.push(format!(
+                "Card {id:>3}: {} | {}",
+                format(winning_no),
+                format(&numbers)
+            ));
+        }
+
+        cards.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
@@ -127,14 +150,35 @@ mod test {
     #[test]
     pub fn test_card() {
         assert_eq!(
-            card("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"),
-            Ok((
+            card("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap(),
+            (
                 "",
                 Card {
                     winning_no: vec![41, 48, 83, 86, 17],
                     numbers: vec![83, 86, 6, 31, 17, 9, 48, 53],
                 }
-            )),
+            ),
+        );
+    }
+
+    #[test]
+    pub fn test_parse_error() {
+        let res = Day04::parse("Card 1: 41 48 | 83 86\nCard 2: 13 32 x 61 30");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(2, 15, "numbers separated by `|`"))
+        );
+
+        let res = Day04::parse("Crd 1: 41 48 | 83 86");
+
+        assert_eq!(res.err(), Some(ParseError::new(1, 1, "`Card <id>:`")));
+
+        let res = Day04::parse("Card 1: 41 480 | 83 86");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(1, 12, "number fitting into `u8`"))
         );
     }
 
@@ -150,7 +194,7 @@ mod test {
 
     #[test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".to_string(),
             "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19".to_string(),
             "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1".to_string(),
@@ -159,14 +203,16 @@ mod test {
             "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11".to_string(),
         ];
 
-        let res = part_one(input);
+        let cards = Day04::parse(&input.join("\n")).unwrap();
+
+        let res = part_one(&cards);
 
         assert_eq!(res, 13);
     }
 
     #[test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".to_string(),
             "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19".to_string(),
             "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1".to_string(),
@@ -175,8 +221,22 @@ mod test {
             "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11".to_string(),
         ];
 
-        let res = part_two(input);
+        let cards = Day04::parse(&input.join("\n")).unwrap();
+
+        let res = part_two(&cards);
 
         assert_eq!(res, 30);
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day04::generate(50, 1);
+        assert_eq!(input, Day04::generate(50, 1));
+
+        let cards = Day04::parse(&input).unwrap();
+        assert_eq!(cards.len(), 50);
+        assert!(cards
+            .iter()
+            .all(|c| c.winning_no.len() == 10 && c.numbers.len() == 25 && c.wins() <= 4));
+    }
 }
@@ -1,146 +1,473 @@
-use indicatif::ParallelProgressIterator;
+mod mapping;
+
+use std::{collections::BTreeMap, ops::Range};
+
+use aoc_common::{
+    parse::{blocks, finish, key_value, lines, spaced, unsigned},
+    seeded_rng, Answer, ErrorTree, Generator, IResult, ParseError, Solution, SolveError,
+};
 use nom::{
-    branch::alt,
-    bytes::complete::{tag, take_till, take_until, take_while1},
-    character::complete::line_ending,
-    combinator::{eof, iterator, map_res, recognize},
-    sequence::{terminated, tuple},
-    IResult,
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, space1},
+    combinator::consumed,
+    error::{context, ContextError, ErrorKind, ParseError as _},
+    sequence::{pair, preceded, tuple},
+};
+use rand::{
+    seq::{index, SliceRandom},
+    Rng,
 };
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
+pub use mapping::*;
 
-    res
+/// A `<source>-to-<dest> map` of the almanach.
+#[derive(Debug, PartialEq)]
+pub struct CategoryMap<'a> {
+    pub source: &'a str,
+    pub dest: &'a str,
+    pub entries: Vec<[u64; 3]>,
 }
 
 #[derive(Debug, PartialEq)]
-struct Almanach {
+pub struct Almanach<'a> {
     pub seeds: Vec<u64>,
-    pub maps: Vec<Vec<[u64; 3]>>,
+    pub maps: Vec<CategoryMap<'a>>,
 }
 
-impl Almanach {
+impl<'a> Almanach<'a> {
+    /// The categories, each pointing to the map leading out of it.
+    pub fn graph(&self) -> BTreeMap<&'a str, &CategoryMap<'a>> {
+        self.maps.iter().map(|m| (m.source, m)).collect()
+    }
+
+    /// The maps leading from the `from` to the `to` category, in the order
+    /// they apply. `None` if the chain does not lead from one to the other.
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<&CategoryMap<'a>>> {
+        let graph = self.graph();
+        let mut path = vec![];
+        let mut category = from;
+
+        while category != to && path.len() < self.maps.len() {
+            let map = graph.get(category)?;
+            path.push(*map);
+            category = map.dest;
+        }
+
+        (category == to).then_some(path)
+    }
+
+    fn chain(&self) -> Vec<&CategoryMap<'a>> {
+        self.path("seed", "location")
+            .expect("chain is validated while parsing")
+    }
+
+    /// Checks that the maps form a single chain from `seed` to `location`,
+    /// that every entry ends within `u64` and that no two entries of a map
+    /// cover the same numbers. Errors point
+    /// at the offending map in `source`, the input the almanach was parsed
+    /// from.
+    pub fn validate(&self, source: &str) -> Result<(), ParseError> {
+        if let Some(map) = self.maps.iter().find(|m| {
+            m.entries.iter().any(|[dest, src, len]| {
+                src.checked_add(*len).is_none() || dest.checked_add(*len).is_none()
+            })
+        }) {
+            return Err(ParseError::at(
+                source,
+                map.source,
+                format!(
+                    "`{}-to-{}` entries ending within `u64`",
+                    map.source, map.dest
+                ),
+            ));
+        }
+
+        if let Some(map) = self.maps.iter().find(|m| has_overlap(&m.entries)) {
+            return Err(ParseError::at(
+                source,
+                map.source,
+                format!(
+                    "`{}-to-{}` entries with disjoint source ranges",
+                    map.source, map.dest
+                ),
+            ));
+        }
+
+        if let Some(map) = self.maps.iter().enumerate().find_map(|(i, m)| {
+            self.maps[..i]
+                .iter()
+                .any(|o| o.source == m.source)
+                .then_some(m)
+        }) {
+            return Err(ParseError::at(
+                source,
+                map.source,
+                format!("only one map from `{}`", map.source),
+            ));
+        }
+
+        let graph = self.graph();
+        let mut chain = vec![];
+        let mut category = "seed";
+
+        while category != "location" {
+            match graph.get(category) {
+                Some(map) if !chain.contains(&map.source) => {
+                    chain.push(map.source);
+                    category = map.dest;
+                }
+                _ => {
+                    return Err(ParseError::at(
+                        source,
+                        &source[source.len()..],
+                        format!("map from `{category}`"),
+                    ))
+                }
+            }
+        }
+
+        match self.maps.iter().find(|m| !chain.contains(&m.source)) {
+            Some(map) => Err(ParseError::at(
+                source,
+                map.source,
+                "map on the chain from `seed` to `location`",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// The maps from `from` to `to` composed into a single function.
+    pub fn mapping_between(&self, from: &str, to: &str) -> Option<Mapping> {
+        let path = self.path(from, to)?;
+
+        Some(
+            path.into_iter()
+                .map(|m| Mapping::from_entries(&m.entries))
+                .collect(),
+        )
+    }
+
+    /// Where the `ranges` of the `from` category end up in the `to` category.
+    pub fn convert_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: Vec<Range<u64>>,
+    ) -> Option<Vec<Range<u64>>> {
+        let path = self.path(from, to)?;
+
+        Some(
+            path.into_iter()
+                .fold(ranges, |ranges, m| map_ranges(&m.entries, ranges)),
+        )
+    }
+
+    /// The seeds of part two, read as pairs of start and length. Parsing
+    /// rejects a seed without a length and empty or overflowing ranges.
+    pub fn seed_ranges(&self) -> Vec<Range<u64>> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|r| r[0]..r[0] + r[1])
+            .collect()
+    }
+
+    /// Pushes the seed ranges through the maps as a whole, splitting them
+    /// where map entries begin or end.
     pub fn lowest_location_from_ranges(&self) -> u64 {
-        let seeds: Vec<u64> = self
-            .seeds
-            .chunks(2)
-            .flat_map(|r| r[0]..r[0] + r[1])
-            .collect();
+        self.chain()
+            .into_iter()
+            .fold(self.seed_ranges(), |ranges, map| {
+                map_ranges(&map.entries, ranges)
+            })
+            .iter()
+            .map(|r| r.start)
+            .min()
+            .expect("parsing rejects empty seed ranges")
+    }
 
-        seeds
+    /// Maps every single seed of the ranges. Far too slow for the real
+    /// input, but simple enough to check [`Self::lowest_location_from_ranges`].
+    pub fn lowest_location_from_ranges_brute_force(&self) -> u64 {
+        self.seed_ranges()
             .into_par_iter()
-            .progress()
-            .map(|s| self.location_for_seed(s))
+            .flat_map_iter(|r| r.map(|s| self.location_for_seed(s)))
             .min()
-            .unwrap()
+            .expect("parsing rejects empty seed ranges")
     }
 
-    pub fn lowest_location(&self) -> u64 {
-        self.seeds
+    /// All maps composed into a single function from seeds to locations.
+    pub fn mapping(&self) -> Mapping {
+        self.chain()
+            .into_iter()
+            .map(|m| Mapping::from_entries(&m.entries))
+            .collect()
+    }
+
+    /// The seeds ending up at `location`.
+    pub fn seeds_for_location(&self, location: u64) -> Vec<u64> {
+        self.mapping().preimage(location)
+    }
+
+    /// The ranges of seeds ending up in `locations`.
+    pub fn seeds_for_locations(&self, locations: Range<u64>) -> Vec<Range<u64>> {
+        self.mapping().preimage_range(locations)
+    }
+
+    /// Walks the locations upward and returns the first one with a seed
+    /// from the seed ranges. Between two places where the image of a
+    /// segment begins or ends, the same segments map to every location, so
+    /// the walk checks the seeds of such a stretch at once and then jumps
+    /// to the next one.
+    pub fn first_location_from_ranges(&self) -> Option<u64> {
+        let mapping = self.mapping();
+        let ranges = self.seed_ranges();
+
+        let mut bounds = mapping
+            .segments()
             .iter()
-            .map(|s| self.location_for_seed(*s))
-            .min()
-            .unwrap()
+            .flat_map(|s| {
+                let image = s.dest_range();
+                [image.start, image.end]
+            })
+            .collect::<Vec<u64>>();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        bounds.windows(2).find_map(|stretch| {
+            mapping
+                .preimage_range(stretch[0]..stretch[1])
+                .into_iter()
+                .flat_map(|seeds| {
+                    ranges.iter().filter_map(move |r| {
+                        let start = seeds.start.max(r.start);
+
+                        (start < seeds.end.min(r.end)).then_some(start)
+                    })
+                })
+                .map(|seed| mapping.apply(seed))
+                .min()
+        })
+    }
+
+    pub fn lowest_location(&self) -> u64 {
+        let mapping = self.mapping();
+
+        self.seeds.iter().map(|s| mapping.apply(*s)).min().unwrap()
     }
 
     pub fn location_for_seed(&self, seed: u64) -> u64 {
-        self.maps
-            .iter()
-            .fold(seed, |seed, map| find_dest_in_map(map.to_vec(), seed))
+        self.chain()
+            .into_iter()
+            .fold(seed, |seed, map| find_dest_in_map(&map.entries, seed))
     }
 }
 
-pub fn find_dest_in_map(map: Vec<[u64; 3]>, seed: u64) -> u64 {
+/// Whether two entries of `map` move some of the same numbers.
+fn has_overlap(map: &[[u64; 3]]) -> bool {
+    let mut sources = map
+        .iter()
+        .filter(|[_, _, len]| *len > 0)
+        .map(|[_, src, len]| (*src, src + len))
+        .collect::<Vec<(u64, u64)>>();
+    sources.sort_unstable();
+
+    sources.windows(2).any(|w| w[0].1 > w[1].0)
+}
+
+pub fn find_dest_in_map(map: &[[u64; 3]], seed: u64) -> u64 {
     match map.iter().find(|e| e[1] <= seed && (e[1] + e[2]) > seed) {
         Some(res) => res[0] + (seed - res[1]),
         None => seed,
     }
 }
 
-pub fn from_str(input: &str) -> Result<u64, std::num::ParseIntError> {
-    input.parse::<u64>()
-}
+/// Maps whole ranges through `map`. The parts of a range overlapping an
+/// entry are moved to its destination, the parts outside of every entry
+/// keep their numbers.
+pub fn map_ranges(map: &[[u64; 3]], ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    let mut mapped = vec![];
+    let mut unmapped = ranges;
 
-pub fn is_digit(input: char) -> bool {
-    input.is_ascii_digit()
-}
+    for &[dest, src, len] in map {
+        let mut rest = vec![];
 
-pub fn parse_digit(input: &str) -> IResult<&str, u64> {
-    let (input, _) = take_till(|c: char| c.is_ascii_digit())(input)?;
-    map_res(take_while1(is_digit), from_str)(input)
-}
+        for range in unmapped {
+            let start = range.start.max(src);
+            let end = range.end.min(src + len);
 
-pub fn parse_line(input: &str) -> IResult<&str, [u64; 3]> {
-    let (line, res) = tuple((parse_digit, parse_digit, parse_digit))(input)?;
+            if start >= end {
+                rest.push(range);
+                continue;
+            }
 
-    Ok((line, res.into()))
-}
+            mapped.push(start - src + dest..end - src + dest);
 
-pub fn parse_endless_line(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, digits) = take_till(|c| c == '\n')(input)?;
+            if range.start < start {
+                rest.push(range.start..start);
+            }
+            if end < range.end {
+                rest.push(end..range.end);
+            }
+        }
 
-    let res: Vec<u64> = iterator(
-        digits,
-        terminated(parse_digit, alt((tag(" "), recognize(eof)))),
-    )
-    .collect();
+        unmapped = rest;
+    }
 
-    Ok((input, res))
+    mapped.extend(unmapped);
+    mapped
 }
 
-fn map(line: &str) -> IResult<&str, Vec<[u64; 3]>> {
-    let (input, _) = take_until(":")(line)?;
-
-    let mut input = input;
-    let mut map_input = input;
-    if line.contains("\n\n") {
-        (input, map_input) = take_until("\n\n")(line)?;
-    }
-    let res: Vec<[u64; 3]> = iterator(
-        map_input,
-        terminated(parse_line, alt((recognize(eof), recognize(line_ending)))),
-    )
-    .collect();
+fn map_entry(input: &str) -> IResult<&str, [u64; 3]> {
+    let (input, (dest, src, len)) = tuple((
+        unsigned,
+        preceded(space1, unsigned),
+        preceded(space1, unsigned),
+    ))(input)?;
 
-    let (input, _) = alt((take_till(|c: char| c.is_alphabetic()), recognize(eof)))(input)?;
+    Ok((input, [dest, src, len]))
+}
 
-    Ok((input, res))
+fn map(input: &str) -> IResult<&str, CategoryMap<'_>> {
+    let (input, (source, _, dest, _)) = tuple((
+        context("category", alpha1),
+        context("`-to-`", tag("-to-")),
+        context("category", alpha1),
+        context("` map:`", tag(" map:")),
+    ))(input)?;
+    let (input, entries) = preceded(line_ending, lines(map_entry))(input)?;
+
+    Ok((
+        input,
+        CategoryMap {
+            source,
+            dest,
+            entries,
+        },
+    ))
 }
 
-fn almanach(input: &str) -> IResult<&str, Almanach> {
-    let (input, _) = take_till(|c| c == ' ')(input)?;
-    let (input, seeds) = terminated(parse_endless_line, recognize(line_ending))(input)?;
+/// The seeds, which part two reads as pairs of start and length. Each pair
+/// has to describe a non-empty range of `u64` numbers.
+fn seeds(input: &str) -> IResult<&str, Vec<u64>> {
+    let (rest, seeds) = spaced(consumed(unsigned::<u64>))(input)?;
+
+    if seeds.len() % 2 == 1 {
+        return Err(nom::Err::Failure(ErrorTree::add_context(
+            input,
+            "seeds in pairs of start and length",
+            ErrorTree::from_error_kind(input, ErrorKind::Verify),
+        )));
+    }
 
-    let mut maps = vec![];
-    let mut input = input;
-    while !input.is_empty() {
-        let (inp, m) = map(input)?;
-        input = inp;
-        maps.push(m);
+    if let Some([(text, _), _]) = seeds
+        .chunks_exact(2)
+        .find(|p| p[1].1 == 0 || p[0].1.checked_add(p[1].1).is_none())
+    {
+        return Err(nom::Err::Failure(ErrorTree::add_context(
+            text,
+            "seed range of a positive length ending within `u64`",
+            ErrorTree::from_error_kind(text, ErrorKind::Verify),
+        )));
     }
 
-    Ok((input, Almanach { seeds, maps }))
+    Ok((rest, seeds.into_iter().map(|(_, n)| n).collect()))
 }
 
-pub fn part_one(inp: Vec<String>) -> u64 {
-    let (_, almanach) = almanach(&inp.join("\n")).unwrap();
+fn almanach(input: &str) -> IResult<&str, Almanach<'_>> {
+    let (input, seeds) = key_value("seeds", seeds)(input)?;
+    let (input, maps) = preceded(
+        context("blank line after the seeds", pair(line_ending, line_ending)),
+        blocks(map),
+    )(input)?;
 
-    almanach.lowest_location()
+    Ok((input, Almanach { seeds, maps }))
 }
 
-pub fn part_two(inp: Vec<String>) -> u64 {
-    let (_, almanach) = almanach(&inp.join("\n")).unwrap();
+pub fn part_one(almanach: &Almanach) -> u64 {
+    almanach.lowest_location()
+}
 
+pub fn part_two(almanach: &Almanach) -> u64 {
     almanach.lowest_location_from_ranges()
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = Almanach<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let almanach = finish(input, almanach)?;
+        almanach.validate(input)?;
+
+        Ok(almanach)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+impl Generator for Day05 {
+    /// `size` seed ranges and `size` entries in each of the seven maps, all
+    /// within `u32`. The seed ranges are at most 100 seeds long, so the
+    /// brute force can still check part two.
+    fn generate(size: usize, seed: u64) -> String {
+        const SPACE: u64 = 1 << 32;
+
+        let mut rng = seeded_rng(seed);
+        let seeds = (0..size)
+            .map(|_| {
+                let start = rng.gen_range(0..SPACE - 100);
+                format!("{start} {}", rng.gen_range(1..=100))
+            })
+            .collect::<Vec<String>>();
+        let mut output = format!("seeds: {}\n", seeds.join(" "));
+
+        // the maps do not have to be in chain order
+        let mut chain = CATEGORIES.windows(2).collect::<Vec<&[&str]>>();
+        chain.shuffle(&mut rng);
+
+        for names in chain {
+            // the source ranges of a map must not overlap
+            let mut bounds = index::sample(&mut rng, SPACE as usize, 2 * size).into_vec();
+            bounds.sort_unstable();
+
+            let mut entries = bounds
+                .chunks(2)
+                .map(|b| {
+                    let (src, len) = (b[0] as u64, (b[1] - b[0]) as u64);
+                    [rng.gen_range(0..=SPACE - len), src, len]
+                })
+                .collect::<Vec<[u64; 3]>>();
+            entries.shuffle(&mut rng);
+
+            output.push_str(&format!("\n{}-to-{} map:\n", names[0], names[1]));
+            for [dest, src, len] in entries {
+                output.push_str(&format!("{dest} {src} {len}\n"));
+            }
+        }
+
+        output
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
@@ -183,49 +510,287 @@ humidity-to-location map:
 56 93 4",
         );
 
+        let (rest, alm) = res.unwrap();
+
+        assert_eq!(rest, "");
+        assert_eq!(alm.seeds, vec![79, 14, 55, 13]);
         assert_eq!(
-            res,
-            Ok((
-                "",
-                Almanach {
-                    seeds: vec![79, 14, 55, 13],
-                    maps: vec![
-                        vec![[50, 98, 2], [52, 50, 48]],
-                        vec![[0, 15, 37], [37, 52, 2], [39, 0, 15]],
-                        vec![[49, 53, 8], [0, 11, 42], [42, 0, 7], [57, 7, 4]],
-                        vec![[88, 18, 7], [18, 25, 70]],
-                        vec![[45, 77, 23], [81, 45, 19], [68, 64, 13]],
-                        vec![[0, 69, 1], [1, 0, 69]],
-                        vec![[60, 56, 37], [56, 93, 4]],
-                    ],
-                }
+            alm.maps
+                .iter()
+                .map(|m| (m.source, m.dest, m.entries.clone()))
+                .collect::<Vec<(&str, &str, Vec<[u64; 3]>)>>(),
+            vec![
+                ("seed", "soil", vec![[50, 98, 2], [52, 50, 48]]),
+                (
+                    "soil",
+                    "fertilizer",
+                    vec![[0, 15, 37], [37, 52, 2], [39, 0, 15]]
+                ),
+                (
+                    "fertilizer",
+                    "water",
+                    vec![[49, 53, 8], [0, 11, 42], [42, 0, 7], [57, 7, 4]]
+                ),
+                ("water", "light", vec![[88, 18, 7], [18, 25, 70]]),
+                (
+                    "light",
+                    "temperature",
+                    vec![[45, 77, 23], [81, 45, 19], [68, 64, 13]]
+                ),
+                ("temperature", "humidity", vec![[0, 69, 1], [1, 0, 69]]),
+                ("humidity", "location", vec![[60, 56, 37], [56, 93, 4]]),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_parse_error() {
+        let res = Day05::parse("seeds 79 14\n\nseed-to-soil map:\n50 98 2");
+
+        assert_eq!(res.err(), Some(ParseError::new(1, 6, "`:`")));
+
+        let res = Day05::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 x 48");
+
+        assert_eq!(res.err(), Some(ParseError::new(5, 4, "number")));
+
+        let res = Day05::parse("seeds: 79 14\n\nseed-map:\n50 98 2");
+
+        assert_eq!(res.err(), Some(ParseError::new(3, 5, "`-to-`")));
+
+        let res = Day05::parse("seeds: 79 14 55\n\nseed-to-location map:\n50 98 2");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(1, 8, "seeds in pairs of start and length"))
+        );
+
+        let res = Day05::parse("seeds: 79 14 55 0\n\nseed-to-location map:\n50 98 2");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(
+                1,
+                14,
+                "seed range of a positive length ending within `u64`"
+            ))
+        );
+
+        let res = Day05::parse("seeds: 18446744073709551615 1\n\nseed-to-location map:\n50 98 2");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(
+                1,
+                8,
+                "seed range of a positive length ending within `u64`"
             ))
         );
     }
 
+    #[test]
+    pub fn test_validate_overlap() {
+        let res = Day05::parse("seeds: 1 2\n\nseed-to-location map:\n10 0 5\n20 4 3\n");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(
+                3,
+                1,
+                "`seed-to-location` entries with disjoint source ranges"
+            ))
+        );
+
+        // adjoining and empty entries do not overlap
+        let res = Day05::parse("seeds: 1 2\n\nseed-to-location map:\n10 0 5\n20 5 3\n30 6 0");
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    pub fn test_validate_overflow() {
+        let res =
+            Day05::parse("seeds: 1 2\n\nseed-to-location map:\n10 0 5\n0 18446744073709551610 5");
+
+        assert!(res.is_ok());
+
+        for entry in ["0 18446744073709551610 6", "18446744073709551610 0 6"] {
+            let input = format!("seeds: 1 2\n\nseed-to-location map:\n{entry}");
+            let res = Day05::parse(&input);
+
+            assert_eq!(
+                res.err(),
+                Some(ParseError::new(
+                    3,
+                    1,
+                    "`seed-to-location` entries ending within `u64`"
+                ))
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_validate() {
+        let res = Day05::parse("seeds: 1 2\n\nseed-to-soil map:\n1 2 3");
+
+        assert_eq!(res.err(), Some(ParseError::new(4, 6, "map from `soil`")));
+
+        let res =
+            Day05::parse("seeds: 1 2\n\nseed-to-location map:\n1 2 3\n\nseed-to-soil map:\n1 2 3");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(6, 1, "only one map from `seed`"))
+        );
+
+        let res =
+            Day05::parse("seeds: 1 2\n\nsoil-to-water map:\n1 2 3\n\nseed-to-location map:\n1 2 3");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(
+                3,
+                1,
+                "map on the chain from `seed` to `location`"
+            ))
+        );
+    }
+
+    #[test]
+    pub fn test_category_queries() {
+        let almanach = Day05::parse(
+            "seeds: 79 14
+
+water-to-light map:
+88 18 7
+18 25 70
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-water map:
+0 15 37
+
+light-to-location map:
+45 77 23",
+        )
+        .unwrap();
+
+        assert_eq!(
+            almanach
+                .path("seed", "light")
+                .unwrap()
+                .iter()
+                .map(|m| m.dest)
+                .collect::<Vec<&str>>(),
+            vec!["soil", "water", "light"]
+        );
+        assert_eq!(almanach.path("light", "soil"), None);
+        assert_eq!(almanach.path("dirt", "soil"), None);
+        assert_eq!(almanach.path("soil", "soil").map(|p| p.len()), Some(0));
+
+        assert_eq!(almanach.location_for_seed(79), 74);
+        assert_eq!(
+            almanach.mapping_between("soil", "light").unwrap().apply(81),
+            74
+        );
+        assert_eq!(
+            almanach.convert_ranges("water", "location", vec![20..30, 200..210]),
+            Some(vec![58..63, 18..23, 200..210])
+        );
+        assert_eq!(almanach.mapping_between("location", "seed"), None);
+    }
+
     #[test]
     pub fn test_map() {
-        let res = map("foo-map:
+        let res = map("foo-to-bar map:
 50 98 2
 52 50 48
 
 ");
 
-        assert_eq!(res, Ok(("", vec![[50, 98, 2], [52, 50, 48],])))
+        assert_eq!(
+            res.unwrap(),
+            (
+                "\n\n",
+                CategoryMap {
+                    source: "foo",
+                    dest: "bar",
+                    entries: vec![[50, 98, 2], [52, 50, 48]],
+                }
+            )
+        );
     }
 
     #[test]
     pub fn test_find_dest_in_map() {
-        assert_eq!(find_dest_in_map(vec![[50, 98, 2], [52, 50, 48]], 79), 81);
-        assert_eq!(find_dest_in_map(vec![[50, 98, 2], [52, 50, 48]], 14), 14);
-        assert_eq!(find_dest_in_map(vec![[50, 98, 2], [52, 50, 48]], 55), 57);
-        assert_eq!(find_dest_in_map(vec![[50, 98, 2], [52, 50, 48]], 13), 13);
+        assert_eq!(find_dest_in_map(&[[50, 98, 2], [52, 50, 48]], 79), 81);
+        assert_eq!(find_dest_in_map(&[[50, 98, 2], [52, 50, 48]], 14), 14);
+        assert_eq!(find_dest_in_map(&[[50, 98, 2], [52, 50, 48]], 55), 57);
+        assert_eq!(find_dest_in_map(&[[50, 98, 2], [52, 50, 48]], 13), 13);
         assert_eq!(
-            find_dest_in_map(vec![[49, 53, 8], [0, 11, 42], [42, 0, 7], [57, 7, 4]], 53),
+            find_dest_in_map(&[[49, 53, 8], [0, 11, 42], [42, 0, 7], [57, 7, 4]], 53),
             49
         );
     }
 
+    #[test]
+    pub fn test_map_ranges() {
+        let map = [[50, 98, 2], [52, 50, 48]];
+
+        assert_eq!(map_ranges(&map, vec![79..93, 55..68]), vec![81..95, 57..70]);
+        assert_eq!(
+            map_ranges(&map, vec![0..10, 100..110]),
+            vec![0..10, 100..110]
+        );
+        assert_eq!(
+            map_ranges(&map, vec![40..100, 120..130]),
+            vec![50..52, 52..100, 40..50, 120..130]
+        );
+    }
+
+    #[test]
+    pub fn test_lowest_location_from_ranges() {
+        for seed in 0..20 {
+            let input = Day05::generate(20, seed);
+            let almanach = Day05::parse(&input).unwrap();
+
+            assert_eq!(
+                almanach.lowest_location_from_ranges(),
+                almanach.lowest_location_from_ranges_brute_force()
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_seeds_for_location() {
+        for seed in 0..10 {
+            let input = Day05::generate(20, seed);
+            let almanach = Day05::parse(&input).unwrap();
+
+            for s in &almanach.seeds {
+                let location = almanach.location_for_seed(*s);
+
+                assert!(almanach.seeds_for_location(location).contains(s));
+                assert!(almanach
+                    .seeds_for_locations(location..location + 1)
+                    .iter()
+                    .any(|r| r.contains(s)));
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_first_location_from_ranges() {
+        for seed in 0..20 {
+            let input = Day05::generate(20, seed);
+            let almanach = Day05::parse(&input).unwrap();
+            let lowest = almanach.lowest_location_from_ranges();
+
+            assert_eq!(almanach.first_location_from_ranges(), Some(lowest));
+            assert_eq!(almanach.lowest_location_from_ranges_brute_force(), lowest);
+        }
+    }
+
     #[test]
     pub fn test_location_for_seed() {
         let (_, alm) = almanach(
@@ -269,11 +834,15 @@ humidity-to-location map:
         assert_eq!(alm.location_for_seed(14), 43);
         assert_eq!(alm.location_for_seed(55), 86);
         assert_eq!(alm.location_for_seed(13), 35);
+
+        assert_eq!(alm.seeds_for_location(82), vec![79]);
+        assert_eq!(alm.seeds_for_location(46), vec![82]);
+        assert_eq!(alm.first_location_from_ranges(), Some(46));
     }
 
     #[test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             "seeds: 79 14 55 13".to_string(),
             "".to_string(),
             "seed-to-soil map:".to_string(),
@@ -309,14 +878,17 @@ humidity-to-location map:
             "56 93 4".to_string(),
         ];
 
-        let res = part_one(input);
+        let input = input.join("\n");
+        let almanach = Day05::parse(&input).unwrap();
+
+        let res = part_one(&almanach);
 
         assert_eq!(res, 35);
     }
 
     #[test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "seeds: 79 14 55 13".to_string(),
             "".to_string(),
             "seed-to-soil map:".to_string(),
@@ -352,8 +924,22 @@ humidity-to-location map:
             "56 93 4".to_string(),
         ];
 
-        let res = part_two(input);
+        let input = input.join("\n");
+        let almanach = Day05::parse(&input).unwrap();
+
+        let res = part_two(&almanach);
 
         assert_eq!(res, 46);
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day05::generate(20, 1);
+        assert_eq!(input, Day05::generate(20, 1));
+
+        let almanach = Day05::parse(&input).unwrap();
+        assert_eq!(almanach.seeds.len(), 40);
+        assert_eq!(almanach.maps.len(), 7);
+        assert!(almanach.maps.iter().all(|m| m.entries.len() == 20));
+    }
 }
@@ -0,0 +1,247 @@
+use std::{fmt, ops::Range};
+
+/// Numbers in `source` move to the range starting at `dest`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Segment {
+    pub source: Range<u64>,
+    pub dest: u64,
+}
+
+impl Segment {
+    pub fn apply(&self, n: u64) -> u64 {
+        n - self.source.start + self.dest
+    }
+
+    pub fn dest_range(&self) -> Range<u64> {
+        self.dest..self.apply(self.source.end)
+    }
+}
+
+/// A piecewise linear function over `0..u64::MAX`, stored as sorted,
+/// adjoining segments. Numbers no map entry covers keep their value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Mapping {
+    segments: Vec<Segment>,
+}
+
+impl Mapping {
+    pub fn identity() -> Self {
+        Self {
+            segments: vec![Segment {
+                source: 0..u64::MAX,
+                dest: 0,
+            }],
+        }
+    }
+
+    /// Builds the function of one almanach map from its `[dest, src, len]`
+    /// entries, filling the gaps between them with the identity. The source
+    /// ranges must not overlap, as [`crate::Almanach::validate`] checks.
+    pub fn from_entries(entries: &[[u64; 3]]) -> Self {
+        let mut entries = entries.to_vec();
+        entries.sort_unstable_by_key(|[_, src, _]| *src);
+
+        let mut segments = vec![];
+        let mut next = 0;
+
+        for [dest, src, len] in entries.into_iter().filter(|[_, _, len]| *len > 0) {
+            if next < src {
+                segments.push(Segment {
+                    source: next..src,
+                    dest: next,
+                });
+            }
+
+            segments.push(Segment {
+                source: src..src + len,
+                dest,
+            });
+            next = src + len;
+        }
+
+        if next < u64::MAX {
+            segments.push(Segment {
+                source: next..u64::MAX,
+                dest: next,
+            });
+        }
+
+        Self { segments }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// The segment containing `n`, found by binary search.
+    pub fn segment(&self, n: u64) -> Option<&Segment> {
+        let i = self.segments.partition_point(|s| s.source.end <= n);
+
+        self.segments.get(i).filter(|s| s.source.contains(&n))
+    }
+
+    pub fn apply(&self, n: u64) -> u64 {
+        self.segment(n).map_or(n, |s| s.apply(n))
+    }
+
+    /// Every number mapped to `n`, in ascending order. Maps may move
+    /// several ranges onto the same numbers, so there can be more than one.
+    pub fn preimage(&self, n: u64) -> Vec<u64> {
+        self.segments
+            .iter()
+            .filter(|s| s.dest_range().contains(&n))
+            .map(|s| n - s.dest + s.source.start)
+            .collect()
+    }
+
+    /// The ranges of numbers mapped into `range`, in ascending order.
+    pub fn preimage_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        self.segments
+            .iter()
+            .filter_map(|s| {
+                let image = s.dest_range();
+                let start = range.start.max(image.start);
+                let end = range.end.min(image.end);
+
+                (start < end)
+                    .then(|| start - s.dest + s.source.start..end - s.dest + s.source.start)
+            })
+            .collect()
+    }
+
+    /// The function applying `self` first and `next` on its result.
+    pub fn then(&self, next: &Mapping) -> Mapping {
+        let mut segments: Vec<Segment> = vec![];
+
+        for segment in &self.segments {
+            let image = segment.dest_range();
+            let first = next
+                .segments
+                .partition_point(|s| s.source.end <= image.start);
+
+            for other in next.segments[first..]
+                .iter()
+                .take_while(|s| s.source.start < image.end)
+            {
+                let start = image.start.max(other.source.start);
+                let end = image.end.min(other.source.end);
+                let composed = Segment {
+                    source: start - segment.dest + segment.source.start
+                        ..end - segment.dest + segment.source.start,
+                    dest: other.apply(start),
+                };
+
+                // merge pieces that continue the previous one
+                match segments.last_mut() {
+                    Some(last)
+                        if last.source.end == composed.source.start
+                            && last.apply(last.source.end) == composed.dest =>
+                    {
+                        last.source.end = composed.source.end;
+                    }
+                    _ => segments.push(composed),
+                }
+            }
+        }
+
+        Mapping { segments }
+    }
+}
+
+impl FromIterator<Mapping> for Mapping {
+    /// Composes the mappings in order.
+    fn from_iter<I: IntoIterator<Item = Mapping>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Mapping::identity(), |acc, m| acc.then(&m))
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, s) in self.segments.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let offset = s.dest as i128 - s.source.start as i128;
+            write!(
+                f,
+                "{}..{} -> {}..{} ({offset:+})",
+                s.source.start,
+                s.source.end,
+                s.dest_range().start,
+                s.dest_range().end
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    pub fn test_from_entries() {
+        let mapping = Mapping::from_entries(&[[50, 98, 2], [52, 50, 48]]);
+
+        assert_eq!(
+            mapping.to_string(),
+            [
+                "0..50 -> 0..50 (+0)",
+                "50..98 -> 52..100 (+2)",
+                "98..100 -> 50..52 (-48)",
+                "100..18446744073709551615 -> 100..18446744073709551615 (+0)",
+            ]
+            .join("\n")
+        );
+        assert_eq!(mapping.apply(79), 81);
+        assert_eq!(mapping.apply(98), 50);
+        assert_eq!(mapping.apply(14), 14);
+        assert_eq!(mapping.segment(99).map(|s| s.dest), Some(50));
+    }
+
+    #[test]
+    pub fn test_then() {
+        let first = Mapping::from_entries(&[[50, 98, 2], [52, 50, 48]]);
+        let second = Mapping::from_entries(&[[0, 15, 37], [37, 52, 2], [39, 0, 15]]);
+        let composed = first.then(&second);
+
+        for n in 0..200 {
+            assert_eq!(composed.apply(n), second.apply(first.apply(n)));
+        }
+        assert!(composed
+            .segments()
+            .windows(2)
+            .all(|s| s[0].source.end == s[1].source.start));
+        assert_eq!(Mapping::identity().then(&first), first);
+    }
+
+    #[test]
+    pub fn test_preimage() {
+        let mapping = Mapping::from_entries(&[[50, 98, 2], [52, 50, 48], [10, 200, 5]]);
+
+        assert_eq!(mapping.preimage(81), vec![79]);
+        assert_eq!(mapping.preimage(50), vec![98]);
+        assert_eq!(mapping.preimage(12), vec![12, 202]);
+        assert_eq!(mapping.preimage(202), vec![]);
+        assert_eq!(
+            mapping.preimage_range(45..55),
+            vec![45..50, 50..53, 98..100]
+        );
+    }
+
+    #[test]
+    pub fn test_almanach_mapping() {
+        for seed in 0..10 {
+            let input = Day05::generate(20, seed);
+            let almanach = Day05::parse(&input).unwrap();
+            let mapping = almanach.mapping();
+
+            for s in &almanach.seeds {
+                assert_eq!(mapping.apply(*s), almanach.location_for_seed(*s));
+            }
+        }
+    }
+}
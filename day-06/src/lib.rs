@@ -1,80 +1,175 @@
+use aoc_common::{
+    parse::{finish, key_value, spaced, unsigned},
+    seeded_rng, Answer, ErrorTree, Generator, IResult, ParseError, Solution, SolveError,
+};
 use nom::{
-    character::complete, character::complete::space1, multi::separated_list1, IResult, Parser,
+    character::complete::line_ending,
+    error::{ContextError, ErrorKind, ParseError as _},
+    sequence::preceded,
 };
-use nom_supreme::{tag::complete::tag, ParserExt};
+use rand::Rng;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
+/// The numbers written next to each other as one, as part two reads them.
+/// `None` when that does not fit into `u64`.
+pub fn join_digits(numbers: &[u64]) -> Option<u64> {
+    numbers.iter().try_fold(0u64, |acc, n| {
+        if acc == 0 {
+            return Some(*n);
+        }
 
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
+        let digits = n.checked_ilog10().map_or(1, |d| d + 1);
+        acc.checked_mul(10u64.checked_pow(digits)?)?.checked_add(*n)
+    })
+}
 
-    res
+/// A line of numbers that still fit into `u64` once joined.
+fn race_line<'a>(key: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<u64>> {
+    move |input| {
+        let (rest, numbers) = key_value(key, spaced(unsigned))(input)?;
+
+        match join_digits(&numbers) {
+            Some(_) => Ok((rest, numbers)),
+            None => Err(nom::Err::Failure(ErrorTree::add_context(
+                input,
+                "numbers fitting into `u64` once joined",
+                ErrorTree::from_error_kind(input, ErrorKind::Verify),
+            ))),
+        }
+    }
 }
 
 pub fn data(input: &str) -> IResult<&str, (Vec<u64>, Vec<u64>)> {
-    let (input, time) = tag("Time:")
-        .precedes(space1)
-        .precedes(separated_list1(space1, complete::u64))
-        .parse(input)?;
-
-    let (input, dist) = tag("\nDistance:")
-        .precedes(space1)
-        .precedes(separated_list1(space1, complete::u64))
-        .parse(input)?;
+    let (input, time) = race_line("Time")(input)?;
+    let (input, dist) = preceded(line_ending, race_line("Distance"))(input)?;
 
     Ok((input, (time, dist)))
 }
 
-pub fn travel_for_hold(hold: u64, time: u64) -> u64 {
-    (time - hold) * hold
+/// How far the boat gets when the button is held for `hold` of `time`
+/// milliseconds. Computed in `u128`, as the product of two `u64` can
+/// overflow `u64`.
+pub fn travel_for_hold(hold: u64, time: u64) -> u128 {
+    u128::from(time - hold) * u128::from(hold)
 }
 
+/// How many holds travel farther than `dist`. The winning holds lie
+/// between the roots of `hold * (time - hold) = dist`, so the shortest one
+/// follows from the quadratic formula. The integer square root only
+/// approximates it, which the last steps correct.
 pub fn find_winning_hold_count(time: u64, dist: u64) -> u64 {
-    for i in 1..time {
-        if travel_for_hold(i, time) <= dist {
-            continue;
-        }
+    let dist = u128::from(dist);
 
-        return time - (2 * i) + 1;
+    if travel_for_hold(time / 2, time) <= dist {
+        return 0;
     }
 
-    0
-}
+    let disc = u128::from(time).pow(2) - 4 * dist;
+    let mut hold = ((u128::from(time) - disc.isqrt()) / 2) as u64;
+
+    while travel_for_hold(hold, time) <= dist {
+        hold += 1;
+    }
+    while hold > 0 && travel_for_hold(hold - 1, time) > dist {
+        hold -= 1;
+    }
 
-pub fn part_one(inp: Vec<String>) -> u64 {
-    let (_, (time, dist)) = data(inp.join("\n").as_str()).unwrap();
+    time - 2 * hold + 1
+}
 
+pub fn part_one(time: &[u64], dist: &[u64]) -> u64 {
     time.iter()
         .zip(dist)
-        .map(|(t, d)| find_winning_hold_count(*t, d))
+        .map(|(t, d)| find_winning_hold_count(*t, *d))
         .product()
 }
 
-pub fn part_two(inp: Vec<String>) -> u64 {
-    let (_, (time, dist)) = data(inp.join("\n").as_str()).unwrap();
+/// # Panics
+///
+/// When the joined numbers do not fit into `u64`, which [`data`] rejects.
+pub fn part_two(time: &[u64], dist: &[u64]) -> u64 {
+    let time = join_digits(time).expect("times are checked while parsing");
+    let dist = join_digits(dist).expect("distances are checked while parsing");
 
-    let time = time
-        .iter()
-        .fold("".to_string(), |acc, t| format!("{acc}{t}"))
-        .parse::<u64>()
-        .unwrap();
+    find_winning_hold_count(time, dist)
+}
 
-    let dist = dist
-        .iter()
-        .fold("".to_string(), |acc, t| format!("{acc}{t}"))
-        .parse::<u64>()
-        .unwrap();
+pub struct Day06;
 
-    find_winning_hold_count(time, dist)
+impl Solution for Day06 {
+    type Input<'a> = (Vec<u64>, Vec<u64>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        finish(input, data)
+    }
+
+    fn part_one((time, dist): &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(time, dist).into())
+    }
+
+    fn part_two((time, dist): &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(time, dist).into())
+    }
+}
+
+impl Generator for Day06 {
+    /// Races with `size` digit times, each with a record that can be beaten.
+    /// Part two glues the numbers together, so the longer the times, the
+    /// fewer races fit into `u64`: nine races of one digit down to a single
+    /// race from nine digits on. Larger sizes stay at nine digits.
+    fn generate(size: usize, seed: u64) -> String {
+        let digits = size.clamp(1, 9) as u32;
+        let races = 9 / digits;
+
+        let mut rng = seeded_rng(seed);
+        let (mut time, mut dist) = (String::from("Time:    "), String::from("Distance:"));
+        let width = 2 * digits as usize;
+
+        for _ in 0..races {
+            let t = rng.gen_range(10u64.pow(digits - 1).max(3)..10u64.pow(digits));
+            let d = rng.gen_range(1..travel_for_hold(t / 2, t) as u64);
+
+            time.push_str(&format!(" {t:>width$}"));
+            dist.push_str(&format!(" {d:>width$}"));
+        }
+
+        format!("{time}\n{dist}")
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::*;
 
+    #[test]
+    pub fn test_parse_error() {
+        let res = Day06::parse("Time:      7  15   30\nDist:  9  40  200");
+
+        assert_eq!(res.err(), Some(ParseError::new(2, 1, "`Distance`")));
+    }
+
+    #[test]
+    pub fn test_join_digits() {
+        assert_eq!(join_digits(&[7, 15, 30]), Some(71530));
+        assert_eq!(join_digits(&[0, 5, 10]), Some(510));
+        assert_eq!(join_digits(&[u64::MAX]), Some(u64::MAX));
+        assert_eq!(join_digits(&[1844674407, 3709551616]), None);
+        assert_eq!(join_digits(&[u64::MAX, 0]), None);
+    }
+
+    #[test]
+    pub fn test_parse_joined_overflow() {
+        let res = Day06::parse("Time: 7 15\nDistance: 1844674407 3709551616");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(
+                2,
+                1,
+                "numbers fitting into `u64` once joined"
+            ))
+        );
+    }
+
     #[test]
     pub fn test_data() {
         let res = data(
@@ -82,12 +177,37 @@ mod test {
 Distance:  9  40  200",
         );
 
-        assert_eq!(res, Ok(("", (vec![7, 15, 30], vec![9, 40, 200]))));
+        assert_eq!(res.unwrap(), ("", (vec![7, 15, 30], vec![9, 40, 200])));
     }
 
     #[test]
     pub fn test_find_winning_hold_count() {
         assert_eq!(find_winning_hold_count(7, 9), 4);
+        assert_eq!(find_winning_hold_count(15, 40), 8);
+        assert_eq!(find_winning_hold_count(30, 200), 9);
+        assert_eq!(find_winning_hold_count(7, 12), 0);
+        assert_eq!(find_winning_hold_count(0, 0), 0);
+        assert_eq!(find_winning_hold_count(2, 0), 1);
+
+        for time in 0..60 {
+            for dist in 0..1000 {
+                let count = (0..=time)
+                    .filter(|h| travel_for_hold(*h, time) > u128::from(dist))
+                    .count();
+
+                assert_eq!(find_winning_hold_count(time, dist), count as u64);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_find_winning_hold_count_overflow() {
+        let res = Day06::parse("Time: 18446744073709551615\nDistance: 18446744073709551614")
+            .map(|(time, dist)| part_two(&time, &dist));
+
+        assert_eq!(res, Ok(18446744073709551612));
+        assert_eq!(find_winning_hold_count(u64::MAX, 0), u64::MAX - 1);
+        assert_eq!(find_winning_hold_count(u64::MAX, u64::MAX), u64::MAX - 3);
     }
 
     #[test]
@@ -104,25 +224,45 @@ Distance:  9  40  200",
 
     #[test]
     pub fn test_part_one() {
-        let input = vec![
-            "Time:      7  15   30".to_string(),
-            "Distance:  9  40  200".to_string(),
-        ];
+        let (_, (time, dist)) = data(
+            "Time:      7  15   30
+Distance:  9  40  200",
+        )
+        .unwrap();
 
-        let res = part_one(input);
+        let res = part_one(&time, &dist);
 
         assert_eq!(res, 288);
     }
 
     #[test]
     pub fn test_part_two() {
-        let input = vec![
-            "Time:      7  15   30".to_string(),
-            "Distance:  9  40  200".to_string(),
-        ];
+        let (_, (time, dist)) = data(
+            "Time:      7  15   30
+Distance:  9  40  200",
+        )
+        .unwrap();
 
-        let res = part_two(input);
+        let res = part_two(&time, &dist);
 
         assert_eq!(res, 71503);
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day06::generate(4, 1);
+        assert_eq!(input, Day06::generate(4, 1));
+
+        for size in 1..=12 {
+            let (time, dist) = Day06::parse(&Day06::generate(size, 1)).unwrap();
+
+            assert_eq!(time.len(), 9 / size.min(9));
+            assert!(time.iter().all(|t| t.to_string().len() == size.min(9)));
+            assert!(time
+                .iter()
+                .zip(&dist)
+                .all(|(t, d)| find_winning_hold_count(*t, *d) > 0));
+            assert!(part_two(&time, &dist) > 0);
+        }
+    }
 }
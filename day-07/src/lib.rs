@@ -1,24 +1,24 @@
-use std::{cmp::Ordering, collections::BTreeMap};
+mod report;
 
+use std::{collections::BTreeMap, fmt};
+
+use aoc_common::{
+    parse::{finish, lines, unsigned},
+    seeded_rng, Answer, Generator, IResult, ParseError, Solution, SolveError,
+};
 use nom::{
-    bytes::complete::{self, take_till, take_while1},
-    character::complete::space1,
-    combinator::{map, map_res},
-    sequence::preceded,
-    IResult,
+    character::complete::{one_of, space0, space1},
+    combinator::{map, recognize},
+    error::context,
+    multi::count,
+    sequence::{preceded, terminated},
 };
+use rand::Rng;
+use serde::Serialize;
+use thiserror::Error;
 use tracing::trace;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
+pub use report::*;
 
 #[derive(Debug, PartialEq, Copy, Clone, Eq, PartialOrd, Ord)]
 pub enum Card {
@@ -35,11 +35,27 @@ pub enum Card {
     Four = 4,
     Three = 3,
     Two = 2,
-    JokerWeak = 1,
     None = 0,
 }
 
 impl Card {
+    /// Every card, from the weakest to the strongest.
+    pub const ALL: [Card; 13] = [
+        Self::Two,
+        Self::Three,
+        Self::Four,
+        Self::Five,
+        Self::Six,
+        Self::Seven,
+        Self::Eight,
+        Self::Nine,
+        Self::Ten,
+        Self::Joker,
+        Self::Queen,
+        Self::King,
+        Self::Ace,
+    ];
+
     pub fn from_string(input: &str) -> Self {
         match input {
             "A" => Self::Ace,
@@ -58,245 +74,373 @@ impl Card {
             &_ => Self::None,
         }
     }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Ace => 'A',
+            Self::King => 'K',
+            Self::Queen => 'Q',
+            Self::Joker => 'J',
+            Self::Ten => 'T',
+            Self::Nine => '9',
+            Self::Eight => '8',
+            Self::Seven => '7',
+            Self::Six => '6',
+            Self::Five => '5',
+            Self::Four => '4',
+            Self::Three => '3',
+            Self::Two => '2',
+            Self::None => '?',
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Hand {
-    pub cards: [Card; 5],
-    pub bid: u64,
-    pub cheat: bool,
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
 }
 
-impl Hand {
-    pub fn cheat(&self) -> [Card; 5] {
-        if !self.cards.contains(&Card::Joker) {
-            return self.cards;
-        }
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum RulesetError {
+    #[error("hands can hold at most 15 cards, not {0}")]
+    HandTooLarge(usize),
 
-        let mut m: BTreeMap<Card, usize> = BTreeMap::new();
+    #[error("at most 14 cards can be ranked, not {0}")]
+    TooManyRanked(usize),
 
-        for c in self.cards {
-            let count = match m.get(&c) {
-                Some(c) => c + 1,
-                None => 1,
-            };
+    #[error("the ruleset deals hands of {expected} cards, not {found}")]
+    HandSize { expected: usize, found: usize },
+}
+
+/// How hands are scored: how many cards they hold, which card is wild and
+/// how the cards rank when comparing hands of the same type.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Ruleset {
+    hand_size: usize,
+    wild: Option<Card>,
+    wild_weak: bool,
+    strengths: [u8; 15],
+}
 
-            m.insert(c, count);
+impl Ruleset {
+    /// `order` lists the cards from the weakest to the strongest, cards
+    /// missing from it are weaker than all of them. With `wild_weak`, the
+    /// wild card is the weakest card when comparing card by card.
+    pub fn new(
+        order: &[Card],
+        wild: Option<Card>,
+        wild_weak: bool,
+        hand_size: usize,
+    ) -> Result<Self, RulesetError> {
+        // the hand type and four bits per card have to fit the key
+        if hand_size > 15 {
+            return Err(RulesetError::HandTooLarge(hand_size));
+        }
+        if order.len() > 14 {
+            return Err(RulesetError::TooManyRanked(order.len()));
         }
 
-        let mut transform = Card::None;
-        let mut max_size = 0;
-        for (card, size) in m.clone() {
-            if card == Card::Joker {
-                continue;
-            }
+        let mut strengths = [0; 15];
+        for (i, c) in order.iter().enumerate() {
+            strengths[*c as usize] = i as u8 + 1;
+        }
 
-            if size < max_size {
-                continue;
-            }
+        if let (Some(w), true) = (wild, wild_weak) {
+            strengths[w as usize] = 0;
+        }
 
-            if size == max_size && card < transform {
-                continue;
-            }
+        Ok(Self {
+            hand_size,
+            wild,
+            wild_weak,
+            strengths,
+        })
+    }
 
-            transform = card;
-            max_size = size;
-        }
-        trace!(?transform);
+    /// The rules of part one: five cards, no wild card.
+    pub fn standard() -> Self {
+        Self::new(&Card::ALL, None, false, 5).expect("the standard rules are valid")
+    }
 
-        let mut cards = self.cards;
-        for (i, c) in cards.into_iter().enumerate() {
-            if c == Card::Joker {
-                cards[i] = transform;
-            }
-        }
-        trace!(?cards);
+    /// The rules of part two: jokers are wild and the weakest card.
+    pub fn jokers_wild() -> Self {
+        Self::new(&Card::ALL, Some(Card::Joker), true, 5).expect("the joker rules are valid")
+    }
 
-        cards
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
     }
 
-    pub fn cards_by_cardinality(&self, cheat: bool) -> Vec<usize> {
-        let mut m: BTreeMap<Card, usize> = BTreeMap::new();
+    pub fn wild(&self) -> Option<Card> {
+        self.wild
+    }
 
-        let mut cards = self.cards;
-        if cheat {
-            cards = self.cheat();
-        }
+    pub fn wild_weak(&self) -> bool {
+        self.wild_weak
+    }
+
+    /// The rank of the card when comparing card by card, higher is stronger.
+    pub fn strength(&self, card: Card) -> u8 {
+        self.strengths[card as usize]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Serialize)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// Classifies the cards by their two largest groups, so hands of more
+    /// than five cards with five or more alike are five of a kind. Wild
+    /// cards join the largest group of the other cards.
+    pub fn of(cards: &[Card], wild: Option<Card>) -> Self {
+        let mut counts = [0usize; 15];
+        let mut wilds = 0;
 
         for c in cards {
-            let count = match m.get(&c) {
-                Some(c) => c + 1,
-                None => 1,
-            };
+            if Some(*c) == wild {
+                wilds += 1;
+            } else {
+                counts[*c as usize] += 1;
+            }
+        }
 
-            m.insert(c, count);
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts[0] += wilds;
+
+        match (counts[0], counts[1]) {
+            (5.., _) => Self::FiveOfAKind,
+            (4, _) => Self::FourOfAKind,
+            (3, 2..) => Self::FullHouse,
+            (3, _) => Self::ThreeOfAKind,
+            (2, 2) => Self::TwoPair,
+            (2, _) => Self::OnePair,
+            _ => Self::HighCard,
         }
+    }
+}
 
-        m.values().copied().collect::<Vec<usize>>()
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::HighCard => "high card",
+            Self::OnePair => "one pair",
+            Self::TwoPair => "two pair",
+            Self::ThreeOfAKind => "three of a kind",
+            Self::FullHouse => "full house",
+            Self::FourOfAKind => "four of a kind",
+            Self::FiveOfAKind => "five of a kind",
+        };
+
+        f.pad(name)
     }
 }
 
-impl Ord for Hand {
-    #[tracing::instrument]
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let mut a = self.cards_by_cardinality(self.cheat);
-        a.sort();
-        a.reverse();
+/// The cards of a hand and its bid. What the hand is worth depends on the
+/// [`Ruleset`] it is scored by.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Hand {
+    pub cards: Vec<Card>,
+    pub bid: u64,
+}
 
-        let mut o = other.cards_by_cardinality(self.cheat);
-        o.sort();
-        o.reverse();
+impl Hand {
+    pub fn new(cards: Vec<Card>, bid: u64) -> Self {
+        Self { cards, bid }
+    }
 
-        for (a_size, b_size) in a.iter().zip(o) {
-            trace!(a_size, b_size);
+    pub fn hand_type(&self, ruleset: &Ruleset) -> HandType {
+        HandType::of(&self.cards, ruleset.wild())
+    }
 
-            let size_comp = a_size.cmp(&b_size);
-            if size_comp != Ordering::Equal {
-                return size_comp;
-            }
+    /// The strength of the hand under `ruleset`: the hand type followed by
+    /// four bits per card, so keys compare like the hands do.
+    pub fn key(&self, ruleset: &Ruleset) -> Result<u64, RulesetError> {
+        Ok(self.score(ruleset)?.key)
+    }
+
+    /// Classifies the hand once and derives its key from that. Fails when
+    /// the hand does not hold as many cards as `ruleset` deals.
+    pub fn score(&self, ruleset: &Ruleset) -> Result<ScoredHand<'_>, RulesetError> {
+        if self.cards.len() != ruleset.hand_size() {
+            return Err(RulesetError::HandSize {
+                expected: ruleset.hand_size(),
+                found: self.cards.len(),
+            });
         }
 
-        let mut cards = self.cards;
-        let mut other_cards = other.cards;
-        if self.cheat {
-            cards = cards.map(|c| {
-                if c == Card::Joker {
-                    return Card::JokerWeak;
-                }
+        let hand_type = self.hand_type(ruleset);
+        let key = self.cards.iter().fold(hand_type as u64, |key, c| {
+            key << 4 | ruleset.strength(*c) as u64
+        });
 
-                c
-            });
-            trace!(?cards);
+        Ok(ScoredHand {
+            hand: self,
+            hand_type,
+            key,
+        })
+    }
 
-            other_cards = other_cards.map(|c| {
-                if c == Card::Joker {
-                    return Card::JokerWeak;
-                }
+    /// The cards with the wild ones replaced by the card of the largest
+    /// group of the others, the stronger one on a tie.
+    pub fn effective_cards(&self, ruleset: &Ruleset) -> Vec<Card> {
+        let Some(wild) = ruleset.wild() else {
+            return self.cards.clone();
+        };
 
-                c
-            });
+        let mut counts: BTreeMap<Card, usize> = BTreeMap::new();
+        for c in self.cards.iter().filter(|c| **c != wild) {
+            *counts.entry(*c).or_default() += 1;
         }
 
-        let cmps = cards
+        let Some((transform, _)) = counts
+            .into_iter()
+            .max_by_key(|(c, n)| (*n, ruleset.strength(*c)))
+        else {
+            return self.cards.clone();
+        };
+        trace!(?transform);
+
+        self.cards
             .iter()
-            .zip(other_cards)
-            .map(|(a, b)| a.cmp(&b))
-            .collect::<Vec<Ordering>>();
+            .map(|c| if *c == wild { transform } else { *c })
+            .collect()
+    }
+}
 
-        for c in cmps {
-            if c != Ordering::Equal {
-                return c;
-            }
-        }
+/// A hand with its type and key under one [`Ruleset`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ScoredHand<'a> {
+    pub hand: &'a Hand,
+    pub hand_type: HandType,
+    pub key: u64,
+}
 
-        Ordering::Equal
-    }
+pub fn hand<'a>(input: &'a str, ruleset: &Ruleset) -> IResult<&'a str, Hand> {
+    let (input, cards) = count(
+        context(
+            "card `A`, `K`, `Q`, `J`, `T` or `2`-`9`",
+            map(recognize(one_of("AKQJT98765432")), Card::from_string),
+        ),
+        ruleset.hand_size(),
+    )(input)?;
+
+    let (input, bid) = preceded(context("space before the bid", space1), unsigned)(input)?;
+
+    Ok((input, Hand::new(cards, bid)))
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+/// The hands scored by `ruleset`, from the weakest to the strongest, so the
+/// rank of a hand is its index plus one.
+pub fn ranked<'a>(
+    hands: &'a [Hand],
+    ruleset: &Ruleset,
+) -> Result<Vec<ScoredHand<'a>>, RulesetError> {
+    let mut hands = hands
+        .iter()
+        .map(|h| h.score(ruleset))
+        .collect::<Result<Vec<ScoredHand>, RulesetError>>()?;
+
+    hands.sort_by_key(|h| h.key);
+    Ok(hands)
 }
 
-pub fn from_str(input: &str) -> Result<u64, std::num::ParseIntError> {
-    input.parse::<u64>()
+/// The winnings of the hands scored by `ruleset`, each bid multiplied by
+/// the rank of its hand.
+pub fn total_winnings(hands: &[Hand], ruleset: &Ruleset) -> Result<u64, RulesetError> {
+    Ok(ranked(hands, ruleset)?
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (i as u64 + 1) * h.hand.bid)
+        .sum::<u64>())
 }
 
-pub fn is_digit(input: char) -> bool {
-    input.is_ascii_digit()
+#[tracing::instrument(skip_all)]
+pub fn part_one(hands: &[Hand]) -> Result<u64, RulesetError> {
+    total_winnings(hands, &Ruleset::standard())
 }
 
-pub fn parse_digit(input: &str) -> IResult<&str, u64> {
-    let (input, _) = take_till(|c: char| c.is_ascii_digit())(input)?;
-    map_res(take_while1(is_digit), from_str)(input)
+#[tracing::instrument(skip_all)]
+pub fn part_two(hands: &[Hand]) -> Result<u64, RulesetError> {
+    total_winnings(hands, &Ruleset::jokers_wild())
 }
 
-pub fn hand(input: &str, cheat: bool) -> IResult<&str, Hand> {
-    let mut input = input;
-    let mut cards = [Card::None; 5];
-    #[allow(clippy::needless_range_loop)]
-    for i in 0..cards.len() {
-        let (inp, card) = map(complete::take(1usize), |c: &str| Card::from_string(c))(input)?;
-        cards[i] = card;
-        input = inp;
-    }
+pub struct Day07;
 
-    let (input, bid) = preceded(space1, parse_digit)(input)?;
+impl Solution for Day07 {
+    type Input<'a> = Vec<Hand>;
 
-    Ok((input, Hand { cards, bid, cheat }))
-}
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let ruleset = Ruleset::standard();
 
-#[tracing::instrument(skip_all)]
-pub fn part_one(inp: Vec<String>) -> u64 {
-    let mut hands = inp
-        .iter()
-        .map(|i| {
-            let (_, h) = hand(i, false).unwrap();
-            h
-        })
-        .collect::<Vec<Hand>>();
+        finish(input, lines(terminated(|i| hand(i, &ruleset), space0)))
+    }
 
-    hands.sort();
-    for hand in hands.iter() {
-        let cards = hand.cards;
-        trace!(?cards);
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input)?.into())
     }
 
-    hands
-        .iter()
-        .enumerate()
-        .map(|(i, h)| (i as u64 + 1) * h.bid)
-        .sum::<u64>()
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input)?.into())
+    }
 }
 
-#[tracing::instrument(skip_all)]
-pub fn part_two(inp: Vec<String>) -> u64 {
-    let mut hands = inp
-        .iter()
-        .map(|i| {
-            let (_, h) = hand(i, true).unwrap();
-            h
-        })
-        .collect::<Vec<Hand>>();
+impl Generator for Day07 {
+    /// `size` hands of five random cards with bids of up to 1000.
+    fn generate(size: usize, seed: u64) -> String {
+        const CARDS: &[u8] = b"AKQJT98765432";
 
-    hands.sort();
-    for hand in hands.iter() {
-        let cards = hand.cards;
-        trace!(?cards);
-    }
+        let mut rng = seeded_rng(seed);
+        let mut hands = Vec::with_capacity(size);
 
-    hands
-        .iter()
-        .enumerate()
-        .map(|(i, h)| (i as u64 + 1) * h.bid)
-        .sum::<u64>()
+        for _ in 0..size {
+            let cards = (0..5)
+                .map(|_| char::from(CARDS[rng.gen_range(0..CARDS.len())]))
+                .collect::<String>();
+
+            hands.push(format!("{cards} {}", rng.gen_range(1..=1000)));
+        }
+
+        hands.join("\n")
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use std::cmp::Ordering;
+
     use crate::*;
 
     #[test_log::test]
-    pub fn test_hand_cheat() {
-        let (_, b) = hand("T55J5 684", false).unwrap();
-        let res = b.cheat();
+    pub fn test_effective_cards() {
+        let jokers = Ruleset::jokers_wild();
+
+        let (_, b) = hand("T55J5 684", &Ruleset::standard()).unwrap();
+        let res = b.effective_cards(&jokers);
 
         assert_eq!(
             res,
             [Card::Ten, Card::Five, Card::Five, Card::Five, Card::Five]
         );
 
-        let (_, b) = hand("T55JT 684", false).unwrap();
-        let res = b.cheat();
+        let (_, b) = hand("T55JT 684", &Ruleset::standard()).unwrap();
+        let res = b.effective_cards(&jokers);
 
         assert_eq!(
             res,
             [Card::Ten, Card::Five, Card::Five, Card::Ten, Card::Ten]
         );
 
-        let (_, b) = hand("T5JJT 684", false).unwrap();
-        let res = b.cheat();
+        let (_, b) = hand("T5JJT 684", &Ruleset::standard()).unwrap();
+        let res = b.effective_cards(&jokers);
 
         assert_eq!(
             res,
@@ -304,57 +448,184 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_hand_type() {
+        let standard = Ruleset::standard();
+        let jokers = Ruleset::jokers_wild();
+        let hand_type = |cards: &str, ruleset| {
+            hand(&format!("{cards} 1"), ruleset)
+                .unwrap()
+                .1
+                .hand_type(ruleset)
+        };
+
+        assert_eq!(hand_type("32T3K", &standard), HandType::OnePair);
+        assert_eq!(hand_type("KK677", &standard), HandType::TwoPair);
+        assert_eq!(hand_type("T55J5", &standard), HandType::ThreeOfAKind);
+        assert_eq!(hand_type("T55J5", &jokers), HandType::FourOfAKind);
+        assert_eq!(hand_type("KTJJT", &jokers), HandType::FourOfAKind);
+        assert_eq!(hand_type("23332", &standard), HandType::FullHouse);
+        assert_eq!(hand_type("2345J", &jokers), HandType::OnePair);
+        assert_eq!(hand_type("JJJJJ", &jokers), HandType::FiveOfAKind);
+        assert_eq!(hand_type("23456", &standard), HandType::HighCard);
+    }
+
     #[test_log::test]
-    pub fn test_hand_partial_cmp() {
-        let (_, a) = hand("32T3K 765", false).unwrap();
-        let (_, b) = hand("T55J5 684", false).unwrap();
+    pub fn test_hand_key() {
+        let standard = Ruleset::standard();
+        let jokers = Ruleset::jokers_wild();
+        let compare = |a: &str, b: &str, ruleset| {
+            let (_, a) = hand(a, ruleset).unwrap();
+            let (_, b) = hand(b, ruleset).unwrap();
 
-        assert_eq!(a.cmp(&b), Ordering::Less);
-        assert_eq!(b.cmp(&a), Ordering::Greater);
+            a.key(ruleset).unwrap().cmp(&b.key(ruleset).unwrap())
+        };
 
-        let (_, a) = hand("KK677 28 ", false).unwrap();
-        let (_, b) = hand("KTJJT 220", false).unwrap();
+        assert_eq!(compare("32T3K 765", "T55J5 684", &standard), Ordering::Less);
+        assert_eq!(
+            compare("T55J5 684", "32T3K 765", &standard),
+            Ordering::Greater
+        );
 
-        assert_eq!(a.cmp(&b), Ordering::Greater);
-        assert_eq!(b.cmp(&a), Ordering::Less);
+        assert_eq!(
+            compare("KK677 28 ", "KTJJT 220", &standard),
+            Ordering::Greater
+        );
+        assert_eq!(compare("KTJJT 220", "KK677 28 ", &standard), Ordering::Less);
 
-        let (_, a) = hand("33332 28 ", false).unwrap();
-        let (_, b) = hand("2AAAA 220", false).unwrap();
+        assert_eq!(
+            compare("33332 28 ", "2AAAA 220", &standard),
+            Ordering::Greater
+        );
+        assert_eq!(compare("2AAAA 220", "33332 28 ", &standard), Ordering::Less);
 
-        assert_eq!(a.cmp(&b), Ordering::Greater);
-        assert_eq!(b.cmp(&a), Ordering::Less);
+        assert_eq!(
+            compare("77888 28 ", "77788 220", &standard),
+            Ordering::Greater
+        );
+        assert_eq!(compare("77788 220", "77888 28 ", &standard), Ordering::Less);
 
-        let (_, a) = hand("77888 28 ", false).unwrap();
-        let (_, b) = hand("77788 220", false).unwrap();
+        // the jokers count as the weakest card in both hands
+        assert_eq!(compare("JKKK2 1", "QQQQ2 1", &jokers), Ordering::Less);
+        assert_eq!(compare("QQQQ2 1", "JKKK2 1", &jokers), Ordering::Greater);
 
-        assert_eq!(a.cmp(&b), Ordering::Greater);
-        assert_eq!(b.cmp(&a), Ordering::Less);
+        // the same hands rank the other way round without wild jokers
+        assert_eq!(compare("KTJJT 220", "QQQJA 483", &standard), Ordering::Less);
+        assert_eq!(
+            compare("KTJJT 220", "QQQJA 483", &jokers),
+            Ordering::Greater
+        );
+    }
 
-        let (_, a) = hand("77788 220", false).unwrap();
-        let (_, b) = hand("77888 28 ", false).unwrap();
+    #[test]
+    pub fn test_ranked() {
+        let hands = Day07::parse("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483").unwrap();
+        let jokers = Ruleset::jokers_wild();
+        let ranked = ranked(&hands, &jokers).unwrap();
+
+        assert_eq!(
+            ranked.iter().map(|s| s.hand.bid).collect::<Vec<u64>>(),
+            [765, 28, 684, 483, 220]
+        );
+        assert!(ranked
+            .iter()
+            .all(|s| s.hand_type == s.hand.hand_type(&jokers)
+                && s.key == s.hand.key(&jokers).unwrap()));
+    }
+
+    #[test]
+    pub fn test_custom_ruleset() {
+        // three card hands, deuces wild but ranked as usual, aces low
+        let mut order = Card::ALL.to_vec();
+        order.rotate_right(1);
+        let deuces = Ruleset::new(&order, Some(Card::Two), false, 3).unwrap();
+
+        let (_, a) = hand("2KQ 1", &deuces).unwrap();
+        let (_, b) = hand("AA3 2", &deuces).unwrap();
+        let (_, c) = hand("KK2 3", &deuces).unwrap();
+        let (_, d) = hand("KK3 4", &deuces).unwrap();
+
+        assert_eq!(a.hand_type(&deuces), HandType::OnePair);
+        assert_eq!(c.hand_type(&deuces), HandType::ThreeOfAKind);
+        assert_eq!(c.effective_cards(&deuces), [Card::King; 3]);
+        assert!(a.key(&deuces).unwrap() > b.key(&deuces).unwrap());
+        assert!(c.key(&deuces).unwrap() > d.key(&deuces).unwrap());
+        // ranked b, a, d, c
+        assert_eq!(total_winnings(&[a, b, c, d], &deuces), Ok(28));
+
+        assert!(hand("KK2Q 1", &deuces).is_err());
+        assert!(Day07::parse("KK2 3").is_err());
+    }
+
+    #[test]
+    pub fn test_ruleset_error() {
+        assert_eq!(
+            Ruleset::new(&Card::ALL, None, false, 16),
+            Err(RulesetError::HandTooLarge(16))
+        );
+        assert_eq!(
+            Ruleset::new(&[Card::Two; 15], None, false, 5),
+            Err(RulesetError::TooManyRanked(15))
+        );
+
+        let triples = Ruleset::new(&Card::ALL, None, false, 3).unwrap();
+        let pairs = Ruleset::new(&Card::ALL, None, false, 2).unwrap();
+        let (_, hand) = hand("KK2 1", &triples).unwrap();
+
+        assert_eq!(
+            hand.score(&pairs),
+            Err(RulesetError::HandSize {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            total_winnings(&[hand], &Ruleset::standard()),
+            Err(RulesetError::HandSize {
+                expected: 5,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    pub fn test_parse_error() {
+        let res = Day07::parse("32T3K 765\nT55X5 684");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(
+                2,
+                4,
+                "card `A`, `K`, `Q`, `J`, `T` or `2`-`9`"
+            ))
+        );
 
-        assert_eq!(a.cmp(&b), Ordering::Less);
-        assert_eq!(b.cmp(&a), Ordering::Greater);
+        let res = Day07::parse("32T3K765");
+
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(1, 6, "space before the bid"))
+        );
     }
 
     #[test]
     pub fn test_hand() {
         assert_eq!(
-            hand("32T3K 765", false),
-            Ok((
+            hand("32T3K 765", &Ruleset::standard()).unwrap(),
+            (
                 "",
-                Hand {
-                    cards: [Card::Three, Card::Two, Card::Ten, Card::Three, Card::King],
-                    bid: 765,
-                    cheat: false,
-                }
-            )),
+                Hand::new(
+                    vec![Card::Three, Card::Two, Card::Ten, Card::Three, Card::King],
+                    765
+                )
+            ),
         );
     }
 
     #[test_log::test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             "32T3K 765".to_string(),
             "T55J5 684".to_string(),
             "KK677 28 ".to_string(),
@@ -362,14 +633,16 @@ mod test {
             "QQQJA 483".to_string(),
         ];
 
-        let res = part_one(input);
+        let hands = Day07::parse(&input.join("\n")).unwrap();
 
-        assert_eq!(res, 6440);
+        let res = part_one(&hands);
+
+        assert_eq!(res, Ok(6440));
     }
 
     #[test_log::test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "32T3K 765".to_string(),
             "T55J5 684".to_string(),
             "KK677 28 ".to_string(),
@@ -377,8 +650,20 @@ mod test {
             "QQQJA 483".to_string(),
         ];
 
-        let res = part_two(input);
+        let hands = Day07::parse(&input.join("\n")).unwrap();
+
+        let res = part_two(&hands);
+
+        assert_eq!(res, Ok(5905));
+    }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day07::generate(50, 1);
+        assert_eq!(input, Day07::generate(50, 1));
 
-        assert_eq!(res, 5905);
+        let hands = Day07::parse(&input).unwrap();
+        assert_eq!(hands.len(), 50);
+        assert!(hands.iter().all(|h| !h.cards.contains(&Card::None)));
     }
 }
@@ -0,0 +1,165 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{ranked, Card, Hand, HandType, Ruleset, RulesetError};
+
+/// Where a hand landed and what it won.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct Standing {
+    pub rank: u64,
+    pub cards: String,
+    /// The cards with the wild ones replaced by what they stand in for.
+    pub effective_cards: String,
+    pub hand_type: HandType,
+    pub bid: u64,
+    pub winnings: u64,
+}
+
+/// Every hand scored by a ruleset, from the lowest rank to the highest.
+/// Displays as a text table.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct Standings {
+    pub standings: Vec<Standing>,
+}
+
+fn cards_to_string(cards: &[Card]) -> String {
+    cards.iter().map(|c| c.to_char()).collect()
+}
+
+impl Standings {
+    pub fn new(hands: &[Hand], ruleset: &Ruleset) -> Result<Self, RulesetError> {
+        let standings = ranked(hands, ruleset)?
+            .iter()
+            .zip(1..)
+            .map(|(s, rank)| Standing {
+                rank,
+                cards: cards_to_string(&s.hand.cards),
+                effective_cards: cards_to_string(&s.hand.effective_cards(ruleset)),
+                hand_type: s.hand_type,
+                bid: s.hand.bid,
+                winnings: rank * s.hand.bid,
+            })
+            .collect();
+
+        Ok(Self { standings })
+    }
+
+    pub fn total_winnings(&self) -> u64 {
+        self.standings.iter().map(|s| s.winnings).sum()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("standings serialize to JSON")
+    }
+}
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = |header: &str, column: fn(&Standing) -> String| {
+            self.standings
+                .iter()
+                .map(|s| column(s).chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(header.len())
+        };
+
+        let rank = width("rank", |s| s.rank.to_string());
+        let cards = width("cards", |s| s.cards.clone());
+        let effective = width("effective", |s| s.effective_cards.clone());
+        let hand_type = width("type", |s| s.hand_type.to_string());
+        let bid = width("bid", |s| s.bid.to_string());
+        let winnings = width("winnings", |s| s.winnings.to_string())
+            .max(self.total_winnings().to_string().len());
+
+        writeln!(
+            f,
+            "{:>rank$} | {:<cards$} | {:<effective$} | {:<hand_type$} | {:>bid$} | {:>winnings$}",
+            "rank", "cards", "effective", "type", "bid", "winnings"
+        )?;
+
+        let separator = format!(
+            "{:-<rank$}-+-{:-<cards$}-+-{:-<effective$}-+-{:-<hand_type$}-+-{:-<bid$}-+-{:-<winnings$}",
+            "", "", "", "", "", ""
+        );
+        writeln!(f, "{separator}")?;
+
+        for s in &self.standings {
+            writeln!(
+                f,
+                "{:>rank$} | {:<cards$} | {:<effective$} | {:<hand_type$} | {:>bid$} | {:>winnings$}",
+                s.rank, s.cards, s.effective_cards, s.hand_type, s.bid, s.winnings
+            )?;
+        }
+
+        // the total sits under the winnings, labelled up to the bids
+        let label = rank + cards + effective + hand_type + bid + 4 * " | ".len();
+
+        writeln!(f, "{separator}")?;
+        write!(
+            f,
+            "{:>label$} | {:>winnings$}",
+            "total",
+            self.total_winnings()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    fn example() -> Vec<Hand> {
+        let input = [
+            "32T3K 765",
+            "T55J5 684",
+            "KK677 28 ",
+            "KTJJT 220",
+            "QQQJA 483",
+        ];
+
+        Day07::parse(&input.join("\n")).unwrap()
+    }
+
+    #[test]
+    pub fn test_standings_table() {
+        let standings = Standings::new(&example(), &Ruleset::jokers_wild()).unwrap();
+
+        assert_eq!(standings.total_winnings(), 5905);
+        assert_eq!(
+            standings.to_string(),
+            [
+                "rank | cards | effective | type           | bid | winnings",
+                "-----+-------+-----------+----------------+-----+---------",
+                "   1 | 32T3K | 32T3K     | one pair       | 765 |      765",
+                "   2 | KK677 | KK677     | two pair       |  28 |       56",
+                "   3 | T55J5 | T5555     | four of a kind | 684 |     2052",
+                "   4 | QQQJA | QQQQA     | four of a kind | 483 |     1932",
+                "   5 | KTJJT | KTTTT     | four of a kind | 220 |     1100",
+                "-----+-------+-----------+----------------+-----+---------",
+                "                                          total |     5905",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    pub fn test_standings_json() {
+        let standings = Standings::new(&example(), &Ruleset::standard()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&standings.to_json()).unwrap();
+
+        assert_eq!(standings.total_winnings(), 6440);
+        assert_eq!(
+            json["standings"][4],
+            serde_json::json!({
+                "rank": 5,
+                "cards": "QQQJA",
+                "effective_cards": "QQQJA",
+                "hand_type": "ThreeOfAKind",
+                "bid": 483,
+                "winnings": 2415,
+            })
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use thiserror::Error;
+
+use crate::{Graph, Instructions, NodeId};
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum GhostError {
+    #[error("no start nodes")]
+    NoStart,
+
+    #[error("the ghost starting at `{0}` never reaches a goal node")]
+    Unreachable(String),
+
+    #[error("the ghosts never stand on goal nodes at the same time")]
+    NoSolution,
+
+    #[error("the steps until the ghosts meet do not fit 64 bits")]
+    Overflow,
+}
+
+/// The walk of one ghost. It repeats once the ghost stands on the same node
+/// at the same instruction again: from step `offset` on, the walk goes
+/// around a cycle of `length` steps.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cycle {
+    pub offset: usize,
+    pub length: usize,
+    /// The steps before the cycle that end on a goal node.
+    pub transient_hits: Vec<usize>,
+    /// The steps in the first round of the cycle that end on a goal node,
+    /// each reached again every `length` steps.
+    pub hits: Vec<usize>,
+}
+
+impl Cycle {
+    /// Finds where the walk from `start` repeats with Brent's algorithm,
+    /// which only needs two walkers over the graph. Standing on the start
+    /// node takes no step, so it only counts as a hit when the cycle returns
+    /// to it.
+    pub fn find(
+        graph: &Graph,
+        start: NodeId,
+        instructions: &Instructions,
+        is_goal: impl Fn(NodeId) -> bool,
+    ) -> Self {
+        // a state is the node and the index of the next instruction
+        let step = |(node, i): (NodeId, usize)| {
+            (
+                graph.next(node, instructions.get(i)),
+                (i + 1) % instructions.directions().len(),
+            )
+        };
+        let start = (start, 0);
+
+        // the cycle length, from the first power of two the hare gets a
+        // whole cycle ahead of
+        let (mut power, mut length) = (1, 1);
+        let (mut tortoise, mut hare) = (start, step(start));
+        while tortoise != hare {
+            if power == length {
+                tortoise = hare;
+                power *= 2;
+                length = 0;
+            }
+            hare = step(hare);
+            length += 1;
+        }
+
+        // walkers a cycle apart meet where the cycle starts
+        let (mut tortoise, mut hare) = (start, start);
+        for _ in 0..length {
+            hare = step(hare);
+        }
+        let mut offset = 0;
+        while tortoise != hare {
+            tortoise = step(tortoise);
+            hare = step(hare);
+            offset += 1;
+        }
+
+        let mut goals = vec![];
+        let mut state = start;
+        for s in 0..offset + length {
+            if is_goal(state.0) {
+                goals.push(s);
+            }
+            state = step(state);
+        }
+
+        let (transient_hits, hits) = goals
+            .into_iter()
+            .filter(|s| *s > 0 || offset == 0)
+            .partition::<Vec<usize>, _>(|s| *s < offset);
+
+        // the start is reached again after one round
+        let mut hits = hits
+            .into_iter()
+            .map(|s| if s == 0 { length } else { s })
+            .collect::<Vec<usize>>();
+        hits.sort_unstable();
+
+        Self {
+            offset,
+            length,
+            transient_hits,
+            hits,
+        }
+    }
+
+    /// Whether the ghost stands on a goal node after `step` steps.
+    pub fn hit_at(&self, step: usize) -> bool {
+        if step < self.offset {
+            return self.transient_hits.contains(&step);
+        }
+
+        let phase = (step - self.offset) % self.length;
+
+        step > 0
+            && self
+                .hits
+                .iter()
+                .any(|h| (h - self.offset) % self.length == phase)
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+/// Combines `t ≡ a (mod m)` and `t ≡ b (mod n)` into `t ≡ c (mod lcm(m, n))`,
+/// for moduli that need not be coprime. `None` when the two contradict, an
+/// error when `lcm(m, n)` does not fit 64 bits.
+pub fn crt((a, m): (u64, u64), (b, n): (u64, u64)) -> Result<Option<(u64, u64)>, GhostError> {
+    let (a, m, b, n) = (a as i128, m as i128, b as i128, n as i128);
+    let g = gcd(m, n);
+
+    if (b - a) % g != 0 {
+        return Ok(None);
+    }
+
+    let lcm = (m / g)
+        .checked_mul(n)
+        .and_then(|lcm| u64::try_from(lcm).ok())
+        .ok_or(GhostError::Overflow)?;
+
+    // below lcm, so these products fit 128 bits
+    let n = n / g;
+    let (_, inverse, _) = extended_gcd(m / g, n);
+    let k = ((b - a) / g).rem_euclid(n) as u128 * inverse.rem_euclid(n) as u128 % n as u128;
+    let c = (a as u128 + m as u128 * k) % lcm as u128;
+
+    Ok(Some((c as u64, lcm)))
+}
+
+/// The first step, at least one, after which every ghost stands on a goal
+/// node.
+pub fn first_common_hit(cycles: &[Cycle]) -> Result<u64, GhostError> {
+    if cycles.is_empty() {
+        return Err(GhostError::NoStart);
+    }
+
+    // steps before a cycle only happen once, so check them one by one
+    let transient = cycles
+        .iter()
+        .flat_map(|c| c.transient_hits.iter().copied())
+        .filter(|s| cycles.iter().all(|c| c.hit_at(*s)))
+        .min()
+        .map(|s| s as u64);
+
+    // all other common steps are a cycle hit of every ghost, found by
+    // combining one hit from each ghost and keeping the combinations that
+    // agree
+    let mut combined = vec![(0u64, 1u64, 0u64)];
+    for cycle in cycles {
+        let mut next = vec![];
+
+        for (residue, modulus, earliest) in &combined {
+            for h in &cycle.hits {
+                let hit = (*h as u64, cycle.length as u64);
+
+                if let Some((residue, modulus)) = crt((*residue, *modulus), hit)? {
+                    next.push((residue, modulus, (*earliest).max(*h as u64)));
+                }
+            }
+        }
+
+        next.sort_unstable();
+        next.dedup();
+        combined = next;
+    }
+
+    let periodic = combined
+        .into_iter()
+        .map(|(residue, modulus, earliest)| {
+            // the first step congruent to the residue that every ghost has
+            // reached its hit by
+            earliest
+                .saturating_sub(residue)
+                .div_ceil(modulus)
+                .checked_mul(modulus)
+                .and_then(|s| s.checked_add(residue))
+                .ok_or(GhostError::Overflow)
+        })
+        .collect::<Result<Vec<u64>, GhostError>>()?
+        .into_iter()
+        .min();
+
+    match (transient, periodic) {
+        (Some(t), Some(p)) => Ok(t.min(p)),
+        (Some(s), None) | (None, Some(s)) => Ok(s),
+        (None, None) => Err(GhostError::NoSolution),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    fn network(input: &str) -> Map<'_> {
+        Day08::parse(input).unwrap()
+    }
+
+    fn cycle(my_map: &Map, start: &str) -> Cycle {
+        let graph = &my_map.graph;
+
+        Cycle::find(graph, graph.id(start).unwrap(), &my_map.instructions, |n| {
+            graph.name(n).ends_with('Z')
+        })
+    }
+
+    #[test]
+    pub fn test_find_cycle() {
+        let input = [
+            "LR",
+            "",
+            "11A = (11B, XXX)",
+            "11B = (XXX, 11Z)",
+            "11Z = (11B, XXX)",
+            "22A = (22B, XXX)",
+            "22B = (22C, 22C)",
+            "22C = (22Z, 22Z)",
+            "22Z = (22B, 22B)",
+            "XXX = (XXX, XXX)",
+        ]
+        .join("\n");
+        let my_map = network(&input);
+
+        assert_eq!(
+            cycle(&my_map, "11A"),
+            Cycle {
+                offset: 1,
+                length: 2,
+                transient_hits: vec![],
+                hits: vec![2],
+            }
+        );
+        assert_eq!(
+            cycle(&my_map, "22A"),
+            Cycle {
+                offset: 1,
+                length: 6,
+                transient_hits: vec![],
+                hits: vec![3, 6],
+            }
+        );
+        assert!(cycle(&my_map, "22A").hit_at(9));
+        assert!(!cycle(&my_map, "22A").hit_at(8));
+    }
+
+    #[test]
+    pub fn test_crt() {
+        assert_eq!(crt((2, 3), (3, 5)), Ok(Some((8, 15))));
+        assert_eq!(crt((1, 4), (3, 6)), Ok(Some((9, 12))));
+        assert_eq!(crt((0, 2), (1, 2)), Ok(None));
+        assert_eq!(crt((0, 1), (5, 7)), Ok(Some((5, 7))));
+
+        // the largest moduli whose lcm still fits, and the first that do not
+        let big = 1 << 32;
+        assert_eq!(
+            crt((1, big - 1), (0, big + 1)),
+            Ok(Some(((1 << 63) + (1 << 31), u64::MAX)))
+        );
+        assert_eq!(crt((1, big), (0, big + 1)), Err(GhostError::Overflow));
+        assert_eq!(
+            crt((5, u64::MAX), (3, u64::MAX - 1)),
+            Err(GhostError::Overflow)
+        );
+    }
+
+    #[test]
+    pub fn test_first_common_hit() {
+        // the first hits are 1 and 2, but the first ghost only returns to
+        // its goal every three steps
+        let input = [
+            "L",
+            "",
+            "11A = (11Z, 11Z)",
+            "11Z = (11B, 11B)",
+            "11B = (11C, 11C)",
+            "11C = (11Z, 11Z)",
+            "22A = (22B, 22B)",
+            "22B = (22Z, 22Z)",
+            "22Z = (22Z, 22Z)",
+        ]
+        .join("\n");
+        let my_map = network(&input);
+
+        assert_eq!(cycle(&my_map, "11A").offset, 1);
+        assert_eq!(part_two(&my_map), Ok(4));
+
+        // goals reached on odd and on even steps only
+        let input = [
+            "L",
+            "",
+            "11A = (11Z, 11Z)",
+            "11Z = (11A, 11A)",
+            "22A = (22B, 22B)",
+            "22B = (22Z, 22Z)",
+            "22Z = (22B, 22B)",
+        ]
+        .join("\n");
+        let my_map = network(&input);
+
+        assert_eq!(cycle(&my_map, "11A").hits, vec![1]);
+        assert_eq!(part_two(&my_map), Err(GhostError::NoSolution));
+
+        // a goal only passed on the way into the cycle
+        let input = [
+            "L",
+            "",
+            "11A = (11Z, 11Z)",
+            "11Z = (11B, 11B)",
+            "11B = (11B, 11B)",
+            "22A = (22Z, 22Z)",
+            "22Z = (22Z, 22Z)",
+            "33A = (33B, 33B)",
+            "33B = (33B, 33B)",
+        ]
+        .join("\n");
+        let my_map = network(&input);
+
+        assert_eq!(cycle(&my_map, "11A").transient_hits, vec![1]);
+        assert_eq!(
+            first_common_hit(&[cycle(&my_map, "11A"), cycle(&my_map, "22A")]),
+            Ok(1)
+        );
+        assert_eq!(
+            part_two(&my_map),
+            Err(GhostError::Unreachable("33A".to_string()))
+        );
+        assert_eq!(first_common_hit(&[]), Err(GhostError::NoStart));
+    }
+}
@@ -0,0 +1,129 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::{Cycle, Direction, Graph, Instructions, NodeId};
+
+impl Graph<'_> {
+    /// The network as a Graphviz digraph, with `..A` and `..Z` nodes
+    /// filled and the names quoted, as they may start with a digit. With
+    /// `walk`, the nodes and edges the walk from that start goes through
+    /// with those instructions are drawn in red, up to where it starts
+    /// repeating.
+    pub fn to_dot(&self, walk: Option<(NodeId, &Instructions)>) -> String {
+        let mut nodes = BTreeSet::new();
+        let mut edges = BTreeSet::new();
+
+        if let Some((start, instructions)) = walk {
+            let cycle = Cycle::find(self, start, instructions, |_| false);
+            let steps = instructions
+                .directions()
+                .iter()
+                .cycle()
+                .take(cycle.offset + cycle.length);
+
+            nodes.insert(start);
+            steps.fold(start, |node, direction| {
+                let next = self.next(node, *direction);
+                nodes.insert(next);
+                edges.insert((node, *direction));
+
+                next
+            });
+        }
+
+        let mut dot = String::from("digraph network {\n    node [shape=circle];\n");
+
+        for node in self.nodes() {
+            let name = self.name(node);
+            let mut attributes = vec![];
+
+            if name.ends_with('A') {
+                attributes.push("style=filled, fillcolor=palegreen");
+            } else if name.ends_with('Z') {
+                attributes.push("shape=doublecircle, style=filled, fillcolor=lightpink");
+            }
+            if nodes.contains(&node) {
+                attributes.push("color=red, penwidth=2");
+            }
+
+            if !attributes.is_empty() {
+                writeln!(dot, "    \"{name}\" [{}];", attributes.join(", ")).unwrap();
+            }
+        }
+
+        for node in self.nodes() {
+            let (left, right) = self.edges(node);
+            let walked = |direction| edges.contains(&(node, direction));
+
+            // one edge for both directions when they lead to the same node,
+            // unless the walk only took one of them
+            let (walked_left, walked_right) = (walked(Direction::Left), walked(Direction::Right));
+            let lines = if left == right && walked_left == walked_right {
+                vec![(left, "L/R", walked_left)]
+            } else {
+                vec![(left, "L", walked_left), (right, "R", walked_right)]
+            };
+
+            for (next, label, walked) in lines {
+                let color = if walked {
+                    ", color=red, penwidth=2"
+                } else {
+                    ""
+                };
+
+                writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\" [label=\"{label}\"{color}];",
+                    self.name(node),
+                    self.name(next)
+                )
+                .unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    pub fn test_to_dot() {
+        let input = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)";
+        let my_map = Day08::parse(input).unwrap();
+        let graph = &my_map.graph;
+
+        assert_eq!(
+            graph.to_dot(None),
+            [
+                "digraph network {",
+                "    node [shape=circle];",
+                "    \"11A\" [style=filled, fillcolor=palegreen];",
+                "    \"11Z\" [shape=doublecircle, style=filled, fillcolor=lightpink];",
+                "    \"11A\" -> \"11B\" [label=\"L\"];",
+                "    \"11A\" -> \"XXX\" [label=\"R\"];",
+                "    \"11B\" -> \"XXX\" [label=\"L\"];",
+                "    \"11B\" -> \"11Z\" [label=\"R\"];",
+                "    \"11Z\" -> \"11B\" [label=\"L\"];",
+                "    \"11Z\" -> \"XXX\" [label=\"R\"];",
+                "    \"XXX\" -> \"XXX\" [label=\"L/R\"];",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let start = graph.id("11A").unwrap();
+        let dot = graph.to_dot(Some((start, &my_map.instructions)));
+
+        assert!(
+            dot.contains("    \"11A\" [style=filled, fillcolor=palegreen, color=red, penwidth=2];")
+        );
+        assert!(dot.contains("    \"11B\" -> \"11Z\" [label=\"R\", color=red, penwidth=2];"));
+        assert!(dot.contains("    \"11Z\" -> \"11B\" [label=\"L\", color=red, penwidth=2];"));
+        assert!(dot.contains("    \"11A\" -> \"XXX\" [label=\"R\"];"));
+        assert!(!dot.contains("\"XXX\" [color=red"));
+    }
+}
@@ -0,0 +1,210 @@
+use std::{collections::BTreeMap, fmt};
+
+/// Which of its two edges to leave a node by.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Left => 'L',
+            Self::Right => 'R',
+        }
+    }
+}
+
+/// The directions the ghosts follow, repeated forever. Never empty, so a
+/// walk always has a next step.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Instructions(Vec<Direction>);
+
+impl Instructions {
+    /// `None` without any direction.
+    pub fn new(directions: Vec<Direction>) -> Option<Self> {
+        (!directions.is_empty()).then_some(Self(directions))
+    }
+
+    pub fn directions(&self) -> &[Direction] {
+        &self.0
+    }
+
+    /// The direction of step `step`, counting from zero.
+    pub fn get(&self, step: usize) -> Direction {
+        self.0[step % self.0.len()]
+    }
+}
+
+impl fmt::Display for Instructions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|d| write!(f, "{}", d.to_char()))
+    }
+}
+
+/// The index of a node in a [`Graph`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct NodeId(pub u32);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// The network with the nodes numbered in name order. Edges point at node
+/// indices, the names are only kept to look nodes up and print them.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Graph<'a> {
+    names: Vec<&'a str>,
+    edges: Vec<[NodeId; 2]>,
+}
+
+impl<'a> Graph<'a> {
+    /// Numbers the nodes of `network`, or returns the first name a node
+    /// leads to that is not defined.
+    pub fn compile(network: &BTreeMap<&'a str, (&'a str, &'a str)>) -> Result<Self, &'a str> {
+        let names = network.keys().copied().collect::<Vec<&str>>();
+        let id = |name: &'a str| {
+            names
+                .binary_search(&name)
+                .map(|i| NodeId(i as u32))
+                .map_err(|_| name)
+        };
+
+        let edges = network
+            .values()
+            .map(|(left, right)| Ok([id(left)?, id(right)?]))
+            .collect::<Result<Vec<[NodeId; 2]>, &str>>()?;
+
+        Ok(Self { names, edges })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.names
+            .binary_search(&name)
+            .ok()
+            .map(|i| NodeId(i as u32))
+    }
+
+    pub fn name(&self, node: NodeId) -> &'a str {
+        self.names[node.index()]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.names.len() as u32).map(NodeId)
+    }
+
+    /// The `(left, right)` nodes `node` leads to.
+    pub fn edges(&self, node: NodeId) -> (NodeId, NodeId) {
+        let [left, right] = self.edges[node.index()];
+        (left, right)
+    }
+
+    /// The node reached from `node` going `direction`.
+    pub fn next(&self, node: NodeId, direction: Direction) -> NodeId {
+        let [left, right] = self.edges[node.index()];
+
+        match direction {
+            Direction::Left => left,
+            Direction::Right => right,
+        }
+    }
+
+    /// The nodes reached after each step from `start`, repeating the
+    /// instructions forever.
+    pub fn walk<'g>(
+        &'g self,
+        start: NodeId,
+        instructions: &'g Instructions,
+    ) -> impl Iterator<Item = NodeId> + 'g {
+        instructions
+            .directions()
+            .iter()
+            .cycle()
+            .scan(start, |node, direction| {
+                *node = self.next(*node, *direction);
+                Some(*node)
+            })
+    }
+}
+
+impl fmt::Display for Graph<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, node) in self.nodes().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let (left, right) = self.edges(node);
+            write!(
+                f,
+                "{} = ({}, {})",
+                self.name(node),
+                self.name(left),
+                self.name(right)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    pub fn test_compile() {
+        let (_, (_, network)) =
+            parse_input("L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        let graph = Graph::compile(&network).unwrap();
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.id("BBB"), Some(NodeId(1)));
+        assert_eq!(graph.id("CCC"), None);
+        assert_eq!(graph.name(NodeId(2)), "ZZZ");
+        assert_eq!(graph.edges(NodeId(0)), (NodeId(1), NodeId(2)));
+        assert_eq!(
+            graph.to_string(),
+            "AAA = (BBB, ZZZ)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)"
+        );
+
+        let (_, (_, network)) = parse_input("L\n\nAAA = (BBB, CCC)\nBBB = (AAA, BBB)").unwrap();
+        assert_eq!(Graph::compile(&network), Err("CCC"));
+    }
+
+    #[test]
+    pub fn test_instructions() {
+        let (_, instructions) = instructions("LRR").unwrap();
+
+        assert_eq!(instructions.to_string(), "LRR");
+        assert_eq!(instructions.get(1), Direction::Right);
+        assert_eq!(instructions.get(3), Direction::Left);
+        assert_eq!(Instructions::new(vec![]), None);
+    }
+
+    #[test]
+    pub fn test_walk() {
+        let input = "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)\nGGG = (GGG, GGG)\nZZZ = (ZZZ, ZZZ)";
+        let my_map = Day08::parse(input).unwrap();
+        let graph = &my_map.graph;
+
+        let names = graph
+            .walk(graph.id("AAA").unwrap(), &my_map.instructions)
+            .take(3)
+            .map(|n| graph.name(n))
+            .collect::<Vec<&str>>();
+
+        assert_eq!(names, ["CCC", "ZZZ", "ZZZ"]);
+    }
+}
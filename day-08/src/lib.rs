@@ -1,145 +1,226 @@
+mod cycle;
+mod dot;
+mod graph;
+mod query;
+
 use std::collections::BTreeMap;
 
-use lcmx::lcmx;
+use aoc_common::{
+    seeded_rng, Answer, ErrorTree, Generator, IResult, ParseError, Solution, SolveError,
+};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_till, take_until},
-    character::complete::{alphanumeric1, line_ending},
-    combinator::{eof, iterator, recognize},
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, line_ending, multispace0},
+    combinator::{all_consuming, map_opt, value},
+    error::{context, ContextError, ErrorKind, ParseError as _},
+    multi::{many1, separated_list1},
     sequence::{delimited, preceded, terminated},
-    IResult,
 };
+use rand::{seq::SliceRandom, Rng};
 use tracing::trace;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
+pub use cycle::*;
+pub use graph::*;
+pub use query::*;
 
 #[derive(Debug, PartialEq)]
 pub struct Map<'a> {
-    pub instructions: &'a str,
-    pub network: BTreeMap<&'a str, (&'a str, &'a str)>,
+    pub instructions: Instructions,
+    pub graph: Graph<'a>,
 }
 
 #[tracing::instrument]
 pub fn node(input: &str) -> IResult<&str, (&str, (&str, &str))> {
-    let (input, name) = take_until(" ")(input)?;
-    let (input, left) = preceded(tag(" = ("), alphanumeric1)(input)?;
-    let (input, right) = delimited(tag(", "), alphanumeric1, tag(")"))(input)?;
+    let (input, name) = context("node name", alphanumeric1)(input)?;
+    let (input, left) = preceded(
+        context("` = (`", tag(" = (")),
+        context("node name", alphanumeric1),
+    )(input)?;
+    let (input, right) = delimited(
+        context("`, `", tag(", ")),
+        context("node name", alphanumeric1),
+        context("`)`", tag(")")),
+    )(input)?;
 
     Ok((input, (name, (left, right))))
 }
 
+/// The nodes by name, each defined only once.
 #[tracing::instrument]
 pub fn network(input: &str) -> IResult<&str, BTreeMap<&str, (&str, &str)>> {
-    let res: BTreeMap<&str, (&str, &str)> = iterator(
-        input,
-        terminated(node, alt((recognize(line_ending), recognize(eof)))),
-    )
-    .collect();
+    let (input, nodes) = separated_list1(line_ending, node)(input)?;
+    let mut res = BTreeMap::new();
+
+    for (name, edges) in nodes {
+        if res.insert(name, edges).is_some() {
+            return Err(nom::Err::Failure(ErrorTree::add_context(
+                name,
+                "node not defined before",
+                ErrorTree::from_error_kind(name, ErrorKind::Verify),
+            )));
+        }
+    }
 
     trace!(?res);
-    Ok(("", res))
+    Ok((input, res))
+}
+
+pub fn instructions(input: &str) -> IResult<&str, Instructions> {
+    map_opt(
+        many1(alt((
+            value(Direction::Left, char('L')),
+            value(Direction::Right, char('R')),
+        ))),
+        Instructions::new,
+    )(input)
 }
 
-pub fn parse_input(input: &str) -> IResult<&str, Map> {
-    let (input, instructions) =
-        terminated(take_till(|c: char| c == '\n'), recognize(line_ending))(input)?;
+/// The instructions and the network by node name.
+#[allow(clippy::type_complexity)]
+pub fn parse_input(input: &str) -> IResult<&str, (Instructions, BTreeMap<&str, (&str, &str)>)> {
+    let (input, instructions) = terminated(
+        context("instructions `L` or `R`", instructions),
+        context("`L`, `R` or a line break", line_ending),
+    )(input)?;
     trace!(input);
-    trace!(instructions);
+    trace!(%instructions);
 
-    let (input, network) = preceded(recognize(line_ending), network)(input)?;
+    let (input, network) = delimited(
+        context("empty line before the network", line_ending),
+        network,
+        multispace0,
+    )(input)?;
     trace!(input);
     trace!(?network);
 
-    Ok((
-        input,
-        Map {
-            instructions,
-            network,
-        },
-    ))
+    Ok((input, (instructions, network)))
 }
 
-pub fn find_destination_steps(
-    start_id: &str,
-    instructions: &[u8],
-    network: BTreeMap<&str, (&str, &str)>,
-    full_match: bool,
-) -> usize {
-    let mut counter: usize = 1;
-    let mut found_dest = false;
-    let mut iterator: usize = 0;
-    let mut node = network.get(start_id).unwrap();
-
-    while !found_dest {
-        trace!(?node);
-        trace!("{}", instructions[iterator]);
-        let next_node = match instructions[iterator] {
-            b'L' => node.0,
-            b'R' => node.1,
-            0_u8..=75_u8 | 77_u8..=81_u8 | 83_u8..=u8::MAX => todo!(),
-        };
-        trace!(?next_node);
-
-        if !full_match && next_node.ends_with('Z') {
-            found_dest = true;
-
-            continue;
-        }
+/// The steps from `AAA` to `ZZZ`.
+pub fn part_one(my_map: &Map) -> Result<usize, GhostError> {
+    let start = my_map.graph.id("AAA").ok_or(GhostError::NoStart)?;
+    let goal = Pattern::Exact("ZZZ".to_string()).mask(&my_map.graph);
 
-        if full_match && next_node == "ZZZ" {
-            found_dest = true;
+    my_map
+        .navigate(start, &goal, false)
+        .map(|route| route.steps)
+        .ok_or_else(|| GhostError::Unreachable("AAA".to_string()))
+}
 
-            continue;
-        }
+/// The steps until all ghosts, starting from the `..A` nodes, stand on
+/// `..Z` nodes at once.
+pub fn part_two(my_map: &Map) -> Result<u64, GhostError> {
+    let instructions = &my_map.instructions;
 
-        node = network.get(next_node).unwrap();
+    let graph = &my_map.graph;
+    let goals = Pattern::Suffix("Z".to_string()).mask(graph);
 
-        iterator = (iterator + 1) % instructions.len();
-        counter += 1;
-    }
+    let cycles = Pattern::Suffix("A".to_string())
+        .select(graph)
+        .into_iter()
+        .map(|n| {
+            let cycle = Cycle::find(graph, n, instructions, |n| goals[n.index()]);
+            trace!(name = graph.name(n), ?cycle);
 
-    trace!(counter);
+            if cycle.hits.is_empty() && cycle.transient_hits.is_empty() {
+                return Err(GhostError::Unreachable(graph.name(n).to_string()));
+            }
 
-    counter
+            Ok(cycle)
+        })
+        .collect::<Result<Vec<Cycle>, GhostError>>()?;
+
+    first_common_hit(&cycles)
 }
 
-pub fn part_one(inp: Vec<String>) -> usize {
-    let binding = inp.join("\n");
-    let (_, my_map) = parse_input(&binding).unwrap();
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Map<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let (_, (instructions, network)) =
+            all_consuming(parse_input)(input).map_err(|e| ParseError::from_nom(input, e))?;
+        let graph =
+            Graph::compile(&network).map_err(|name| ParseError::at(input, name, "defined node"))?;
 
-    let instructions = my_map.instructions.as_bytes();
-    trace!(?instructions);
-    trace!(?my_map.network);
+        Ok(Map {
+            instructions,
+            graph,
+        })
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input)?.into())
+    }
 
-    let network_keys = my_map.network.keys().copied().collect::<Vec<&str>>();
-    let first_node_id = network_keys.first().unwrap();
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input)?.into())
+    }
+}
 
-    find_destination_steps(first_node_id, instructions, my_map.network, true)
+/// All 676 three letter node names ending in `last`.
+fn node_names(last: u8) -> impl Iterator<Item = String> {
+    (b'A'..=b'Z')
+        .flat_map(move |a| (b'A'..=b'Z').map(move |b| String::from_utf8(vec![a, b, last]).unwrap()))
 }
 
-pub fn part_two(inp: Vec<String>) -> usize {
-    let binding = inp.join("\n");
-    let (_, my_map) = parse_input(&binding).unwrap();
-    let instructions = my_map.instructions.as_bytes();
+impl Generator for Day08 {
+    /// Six ghosts walking from a `..A` to a `..Z` node through about `size`
+    /// nodes, one of them from `AAA` to `ZZZ`. Every node leads one step
+    /// further whichever way the instructions say, and the walk lengths are
+    /// multiples of distinct primes, which keeps their LCM small.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+        let base = (size / 82).max(1);
+
+        let mut primes = [2, 3, 5, 7, 11, 13];
+        primes.shuffle(&mut rng);
+
+        let mut starts = node_names(b'A').skip(1).collect::<Vec<String>>();
+        starts.shuffle(&mut rng);
+        starts.insert(0, "AAA".to_string());
+
+        let mut ends = node_names(b'Z').collect::<Vec<String>>();
+        ends.pop();
+        ends.shuffle(&mut rng);
+        ends.insert(0, "ZZZ".to_string());
+
+        let mut middles = (b'B'..b'Z').flat_map(node_names).collect::<Vec<String>>();
+        assert!(
+            middles.len() >= 82 * base,
+            "not enough node names for {size} nodes"
+        );
+        middles.shuffle(&mut rng);
+        let mut middles = middles.into_iter();
+
+        let mut network = Vec::with_capacity(82 * base);
+        for (ghost, prime) in primes.into_iter().enumerate() {
+            let (start, end) = (&starts[ghost], &ends[ghost]);
+            let levels = (1..base * prime)
+                .map(|_| (middles.next().unwrap(), middles.next().unwrap()))
+                .collect::<Vec<(String, String)>>();
+            let node = |name: &str, level: usize| match levels.get(level) {
+                Some((left, right)) => format!("{name} = ({left}, {right})"),
+                None => format!("{name} = ({end}, {end})"),
+            };
+
+            network.push(node(start, 0));
+            for (level, (left, right)) in levels.iter().enumerate() {
+                network.push(node(left, level + 1));
+                network.push(node(right, level + 1));
+            }
+            network.push(node(end, 0));
+        }
+        network.shuffle(&mut rng);
 
-    let start_nodes = my_map
-        .network
-        .keys()
-        .copied()
-        .filter(|n| n.ends_with('A'))
-        .map(|n| find_destination_steps(n, instructions, my_map.network.clone(), false) as u64)
-        .collect::<Vec<u64>>();
+        let instructions = (0..rng.gen_range(200..300))
+            .map(|_| if rng.gen() { 'L' } else { 'R' })
+            .collect::<String>();
 
-    lcmx(&start_nodes).unwrap() as usize
+        format!("{instructions}\n\n{}", network.join("\n"))
+    }
 }
 
 #[cfg(test)]
@@ -160,12 +241,13 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)
 "
-            ),
-            Ok((
+            )
+            .unwrap(),
+            (
                 "",
-                Map {
-                    instructions: "RL",
-                    network: BTreeMap::from([
+                (
+                    Instructions::new(vec![Direction::Right, Direction::Left]).unwrap(),
+                    BTreeMap::from([
                         ("AAA", ("BBB", "CCC")),
                         ("BBB", ("DDD", "EEE")),
                         ("CCC", ("ZZZ", "GGG")),
@@ -174,14 +256,17 @@ ZZZ = (ZZZ, ZZZ)
                         ("GGG", ("GGG", "GGG")),
                         ("ZZZ", ("ZZZ", "ZZZ")),
                     ])
-                }
-            ))
+                )
+            )
         )
     }
 
     #[test_log::test]
     pub fn test_node() {
-        assert_eq!(node("AAA = (BBB, CCC)"), Ok(("", ("AAA", ("BBB", "CCC")))));
+        assert_eq!(
+            node("AAA = (BBB, CCC)").unwrap(),
+            ("", ("AAA", ("BBB", "CCC")))
+        );
     }
 
     #[test_log::test]
@@ -195,8 +280,9 @@ DDD = (DDD, DDD)
 EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)"
-            ),
-            Ok((
+            )
+            .unwrap(),
+            (
                 "",
                 BTreeMap::from([
                     ("AAA", ("BBB", "CCC")),
@@ -207,7 +293,34 @@ ZZZ = (ZZZ, ZZZ)"
                     ("GGG", ("GGG", "GGG")),
                     ("ZZZ", ("ZZZ", "ZZZ")),
                 ])
-            ))
+            )
+        );
+    }
+
+    #[test_log::test]
+    pub fn test_parse_error() {
+        let res = Day08::parse("RXL\n\nAAA = (AAA, AAA)");
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(1, 2, "`L`, `R` or a line break"))
+        );
+
+        let res = Day08::parse("RL\n\nAAA = (BBB, CCC\nBBB = (AAA, AAA)");
+        assert_eq!(res.err(), Some(ParseError::new(3, 16, "`)`")));
+
+        let res = Day08::parse("RL\n\nAAA = (BBB, AAA)\nBBB = (AAA, CCC)");
+        assert_eq!(res.err(), Some(ParseError::new(4, 13, "defined node")));
+
+        let res = Day08::parse("\n\nAAA = (AAA, AAA)");
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(1, 1, "instructions `L` or `R`"))
+        );
+
+        let res = Day08::parse("RL\n\nAAA = (BBB, AAA)\nBBB = (AAA, AAA)\nAAA = (AAA, BBB)");
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(5, 1, "node not defined before"))
         );
     }
 
@@ -225,9 +338,12 @@ ZZZ = (ZZZ, ZZZ)"
             "ZZZ = (ZZZ, ZZZ)".to_string(),
         ];
 
-        let res = part_one(input);
+        let input = input.join("\n");
+        let my_map = Day08::parse(&input).unwrap();
 
-        assert_eq!(res, 2);
+        let res = part_one(&my_map);
+
+        assert_eq!(res, Ok(2));
     }
 
     #[test_log::test]
@@ -245,8 +361,24 @@ ZZZ = (ZZZ, ZZZ)"
             "XXX = (XXX, XXX)".to_string(),
         ];
 
-        let res = part_two(input);
+        let input = input.join("\n");
+        let my_map = Day08::parse(&input).unwrap();
+
+        let res = part_two(&my_map);
+
+        assert_eq!(res, Ok(6));
+    }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day08::generate(1000, 1);
+        assert_eq!(input, Day08::generate(1000, 1));
+
+        let my_map = Day08::parse(&input).unwrap();
+        assert_eq!(my_map.graph.len(), 984);
 
-        assert_eq!(res, 6);
+        let steps = part_one(&my_map).unwrap();
+        assert_eq!(steps % 12, 0);
+        assert_eq!(part_two(&my_map), Ok(12 * 30030));
     }
 }
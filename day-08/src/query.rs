@@ -0,0 +1,179 @@
+use regex::Regex;
+
+use crate::{Graph, Map, NodeId};
+
+/// Selects nodes by name.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Exact(String),
+    Suffix(String),
+    Regex(Regex),
+    Nodes(Vec<String>),
+}
+
+impl Pattern {
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Exact(exact) => name == exact,
+            Self::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            Self::Regex(regex) => regex.is_match(name),
+            Self::Nodes(nodes) => nodes.iter().any(|n| n == name),
+        }
+    }
+
+    /// The nodes of `graph` matching, in name order.
+    pub fn select(&self, graph: &Graph) -> Vec<NodeId> {
+        graph
+            .nodes()
+            .filter(|n| self.matches(graph.name(*n)))
+            .collect()
+    }
+
+    /// Whether each node of `graph` matches, by node index, so walks can
+    /// check their goal without comparing names.
+    pub fn mask(&self, graph: &Graph) -> Vec<bool> {
+        graph.nodes().map(|n| self.matches(graph.name(n))).collect()
+    }
+}
+
+/// A walk from a start node to the first goal node.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Route {
+    pub start: NodeId,
+    pub steps: usize,
+    /// The nodes visited, from the start to the goal, when asked for.
+    pub path: Option<Vec<NodeId>>,
+}
+
+impl Map<'_> {
+    /// Follows the instructions from `start` until reaching a node in the
+    /// `goal` mask. `None` once the walk has been through every node and
+    /// instruction pair without a goal, as it only repeats from there.
+    pub fn navigate(&self, start: NodeId, goal: &[bool], with_path: bool) -> Option<Route> {
+        let limit = self.graph.len() * self.instructions.directions().len();
+        let mut path = with_path.then(|| vec![start]);
+
+        for (i, node) in self
+            .graph
+            .walk(start, &self.instructions)
+            .take(limit)
+            .enumerate()
+        {
+            if let Some(path) = path.as_mut() {
+                path.push(node);
+            }
+
+            if goal[node.index()] {
+                return Some(Route {
+                    start,
+                    steps: i + 1,
+                    path,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// The route from every node matching `starts` to the first node
+    /// matching `goal`, `None` for the starts never getting there.
+    pub fn navigate_all(
+        &self,
+        starts: &Pattern,
+        goal: &Pattern,
+        with_path: bool,
+    ) -> Vec<(NodeId, Option<Route>)> {
+        let goal = goal.mask(&self.graph);
+
+        starts
+            .select(&self.graph)
+            .into_iter()
+            .map(|start| (start, self.navigate(start, &goal, with_path)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use regex::Regex;
+
+    use crate::*;
+
+    fn example() -> String {
+        [
+            "RL",
+            "",
+            "AAA = (BBB, CCC)",
+            "BBB = (DDD, EEE)",
+            "CCC = (ZZZ, GGG)",
+            "DDD = (DDD, DDD)",
+            "EEE = (EEE, EEE)",
+            "GGG = (GGG, GGG)",
+            "ZZZ = (ZZZ, ZZZ)",
+        ]
+        .join("\n")
+    }
+
+    #[test]
+    pub fn test_pattern() {
+        let input = example();
+        let my_map = Day08::parse(&input).unwrap();
+        let names = |pattern: Pattern| {
+            pattern
+                .select(&my_map.graph)
+                .into_iter()
+                .map(|n| my_map.graph.name(n))
+                .collect::<Vec<&str>>()
+        };
+
+        assert_eq!(names(Pattern::Exact("CCC".to_string())), ["CCC"]);
+        assert_eq!(names(Pattern::Suffix("Z".to_string())), ["ZZZ"]);
+        assert_eq!(
+            names(Pattern::Regex(Regex::new("^[A-C]").unwrap())),
+            ["AAA", "BBB", "CCC"]
+        );
+        assert_eq!(
+            names(Pattern::Nodes(vec!["GGG".to_string(), "XXX".to_string()])),
+            ["GGG"]
+        );
+    }
+
+    #[test]
+    pub fn test_navigate() {
+        let input = example();
+        let my_map = Day08::parse(&input).unwrap();
+        let graph = &my_map.graph;
+        let id = |name| graph.id(name).unwrap();
+
+        let routes = my_map.navigate_all(
+            &Pattern::Nodes(vec!["AAA".to_string(), "BBB".to_string()]),
+            &Pattern::Suffix("Z".to_string()),
+            true,
+        );
+
+        assert_eq!(
+            routes,
+            [
+                (
+                    id("AAA"),
+                    Some(Route {
+                        start: id("AAA"),
+                        steps: 2,
+                        path: Some(vec![id("AAA"), id("CCC"), id("ZZZ")]),
+                    })
+                ),
+                (id("BBB"), None),
+            ]
+        );
+
+        let goal = Pattern::Exact("GGG".to_string()).mask(graph);
+        assert_eq!(
+            my_map.navigate(id("CCC"), &goal, false),
+            Some(Route {
+                start: id("CCC"),
+                steps: 1,
+                path: None,
+            })
+        );
+    }
+}
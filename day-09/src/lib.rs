@@ -1,45 +1,76 @@
+mod polynomial;
+
+use aoc_common::{
+    seeded_rng, Answer, ErrorTree, Generator, IResult, ParseError, Solution, SolveError,
+};
 use nom::{
     branch::alt,
     character::complete::{self, line_ending, space1},
-    combinator::{eof, recognize},
-    multi::{many1, separated_list1},
-    sequence::terminated,
-    IResult,
+    combinator::{all_consuming, cut, eof, recognize},
+    error::{context, ContextError, ErrorKind, FromExternalError},
+    multi::{many0, many1},
+    sequence::{preceded, terminated},
 };
+use rand::Rng;
+use thiserror::Error;
 use tracing::trace;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
+pub use polynomial::*;
 
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Error)]
+pub enum SeriesError {
+    #[error("the series does not fit 128 bits at index {index}")]
+    Overflow { index: i128 },
+
+    /// The differences `depth` rows down do not fit, starting with the one
+    /// taken from the values at `index` and on of the series.
+    #[error(
+        "the differences of depth {depth} from index {index} of the series do not fit 128 bits"
+    )]
+    DifferenceOverflow { depth: usize, index: usize },
 
-    res
+    #[error("line {line} is not the values of a polynomial")]
+    NotPolynomial { line: usize },
 }
 
-#[derive(Debug, PartialEq)]
+/// What kind of sequence a series is.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Shape {
+    /// The values of a polynomial of `degree`, with its coefficients in the
+    /// binomial basis, `coefficients[k]` being the factor of
+    /// `binomial(index, k)`. The last one is the constant `degree`-th
+    /// difference.
+    Polynomial {
+        degree: usize,
+        coefficients: Vec<i128>,
+    },
+    /// The differences run out of values before a row of zeros shows up.
+    NotRepresentable,
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Series {
-    pub initial: Vec<i64>,
-    pub extrapolation: Vec<Vec<i64>>,
+    pub initial: Vec<i128>,
+    pub extrapolation: Vec<Vec<i128>>,
 }
 
 impl Series {
-    pub fn from_initial(initial: Vec<i64>) -> Self {
-        let extrapolation = Self::extrapolate(initial.clone());
+    /// Builds the difference table of `initial`, failing when a difference
+    /// does not fit 128 bits.
+    pub fn from_initial(initial: Vec<i128>) -> Result<Self, SeriesError> {
+        let extrapolation = Self::extrapolate(initial.clone())?;
 
-        Self {
+        Ok(Self {
             initial,
             extrapolation,
-        }
+        })
     }
 
     #[tracing::instrument]
     pub fn extend_by_one(&mut self) {
         let mut prev_num = 0;
 
-        let last_nums: Vec<i64> = self
+        let last_nums: Vec<i128> = self
             .extrapolation
             .iter()
             .rev()
@@ -64,7 +95,7 @@ impl Series {
     pub fn extend_left_by_one(&mut self) {
         let mut prev_num = 0;
 
-        let first_nums: Vec<i64> = self
+        let first_nums: Vec<i128> = self
             .extrapolation
             .iter()
             .rev()
@@ -88,30 +119,126 @@ impl Series {
         self.initial.insert(0, first_num - prev_num);
     }
 
+    /// The degree of the polynomial the values are of, the number of
+    /// difference rows before a row of zeros. `None` when the rows shrink
+    /// to nothing without one.
+    pub fn degree(&self) -> Option<usize> {
+        match self.extrapolation.last() {
+            None => Some(0),
+            Some(zeros) if !zeros.is_empty() => Some(self.extrapolation.len() - 1),
+            Some(_) => None,
+        }
+    }
+
+    pub fn shape(&self) -> Shape {
+        match self.degree() {
+            Some(degree) => Shape::Polynomial {
+                degree,
+                coefficients: self.forward_differences().take(degree + 1).collect(),
+            },
+            None => Shape::NotRepresentable,
+        }
+    }
+
+    /// The first value of the series and of each difference row, the
+    /// coefficients of Newton's forward-difference formula.
+    pub fn forward_differences(&self) -> impl Iterator<Item = i128> + '_ {
+        self.initial
+            .first()
+            .into_iter()
+            .chain(self.extrapolation.iter().filter_map(|d| d.first()))
+            .copied()
+    }
+
+    /// The value at `index`, `0` being the first value of the series and
+    /// negative indices preceding it, from the sum of the forward
+    /// differences times `binomial(index, k)`.
+    pub fn value_at(&self, index: i128) -> Result<i128, SeriesError> {
+        let overflow = SeriesError::Overflow { index };
+        let mut binomial: i128 = 1;
+        let mut value: i128 = 0;
+
+        // trailing zero differences add nothing, but their binomials may
+        // still overflow
+        let terms = self
+            .forward_differences()
+            .enumerate()
+            .filter(|(_, d)| *d != 0)
+            .last()
+            .map_or(0, |(k, _)| k + 1);
+
+        for (k, difference) in self.forward_differences().take(terms).enumerate() {
+            if k > 0 {
+                // binomial(index, k) = binomial(index, k - 1) * (index - k + 1) / k,
+                // dividing first so it only overflows when the result does
+                let k = k as i128;
+                // a divisor of k, so it fits
+                let divisor = gcd(binomial, k) as i128;
+                let factor = index.checked_sub(k - 1).ok_or(overflow)? / (k / divisor);
+                binomial = (binomial / divisor).checked_mul(factor).ok_or(overflow)?;
+            }
+
+            let term = binomial.checked_mul(difference).ok_or(overflow)?;
+            value = value.checked_add(term).ok_or(overflow)?;
+        }
+
+        Ok(value)
+    }
+
     #[tracing::instrument]
-    fn extrapolate(initial: Vec<i64>) -> Vec<Vec<i64>> {
+    fn extrapolate(initial: Vec<i128>) -> Result<Vec<Vec<i128>>, SeriesError> {
         let mut output = vec![];
 
         let mut current = initial;
         while !current.iter().all(|c| *c == 0) {
-            let diff = Self::get_diff(current);
+            let diff = Self::get_diff(current, output.len() + 1)?;
             trace!(?diff);
             output.push(diff.clone());
             current = diff;
         }
 
-        output
+        Ok(output)
     }
 
-    fn get_diff(a: Vec<i64>) -> Vec<i64> {
-        a.windows(2).map(|i| i[1] - i[0]).collect()
+    /// The differences of `a`, the row `depth` below the series.
+    fn get_diff(a: Vec<i128>, depth: usize) -> Result<Vec<i128>, SeriesError> {
+        a.windows(2)
+            .enumerate()
+            .map(|(index, i)| {
+                i[1].checked_sub(i[0])
+                    .ok_or(SeriesError::DifferenceOverflow { depth, index })
+            })
+            .collect()
     }
 }
 
-pub fn parse_series(input: &str) -> IResult<&str, Series> {
-    let (input, initial) = separated_list1(space1, complete::i64)(input)?;
+/// The greatest common divisor of the magnitudes, `0` only for two zeros.
+/// Unsigned, as the one of `i128::MIN` and `0` does not fit `i128`.
+fn gcd(a: i128, b: i128) -> u128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
 
-    Ok((input, Series::from_initial(initial)))
+    a
+}
+
+pub fn parse_series(input: &str) -> IResult<&str, Series> {
+    let start = input;
+    let (input, first) = context("integer", complete::i64)(input)?;
+    let (input, rest) = many0(preceded(space1, cut(context("integer", complete::i64))))(input)?;
+
+    let initial = std::iter::once(first).chain(rest).map(i128::from).collect();
+
+    match Series::from_initial(initial) {
+        Ok(series) => Ok((input, series)),
+        Err(e) => Err(nom::Err::Failure(ErrorTree::add_context(
+            start,
+            "differences fitting 128 bits",
+            ErrorTree::from_external_error(start, ErrorKind::MapRes, e),
+        ))),
+    }
 }
 
 #[tracing::instrument]
@@ -122,32 +249,78 @@ pub fn parse_input(input: &str) -> IResult<&str, Vec<Series>> {
     ))(input)
 }
 
-pub fn part_one(inp: Vec<String>) -> i64 {
-    let inp = inp.join("\n");
-
-    let (_, mut series) = parse_input(&inp).unwrap();
-
+/// The sum of the values at `index` of every series, which all have to be
+/// polynomial.
+fn sum_at(series: &[Series], index: impl Fn(&Series) -> i128) -> Result<i128, SeriesError> {
     series
-        .iter_mut()
-        .map(|s| {
-            s.extend_by_one();
-            *s.initial.last().unwrap()
+        .iter()
+        .enumerate()
+        .map(|(i, s)| match s.degree() {
+            Some(_) => s.value_at(index(s)),
+            None => Err(SeriesError::NotPolynomial { line: i + 1 }),
         })
-        .sum::<i64>()
+        .sum()
 }
 
-pub fn part_two(inp: Vec<String>) -> i64 {
-    let inp = inp.join("\n");
+pub fn part_one(series: &[Series]) -> Result<i128, SeriesError> {
+    sum_at(series, |s| s.initial.len() as i128)
+}
 
-    let (_, mut series) = parse_input(&inp).unwrap();
+pub fn part_two(series: &[Series]) -> Result<i128, SeriesError> {
+    sum_at(series, |_| -1)
+}
 
-    series
-        .iter_mut()
-        .map(|s| {
-            s.extend_left_by_one();
-            *s.initial.first().unwrap()
-        })
-        .sum::<i64>()
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = Vec<Series>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        all_consuming(parse_input)(input)
+            .map(|(_, s)| s)
+            .map_err(|e| ParseError::from_nom(input, e))
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input)?.into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input)?.into())
+    }
+}
+
+impl Generator for Day09 {
+    /// `size` lines of 21 values of a random polynomial of up to the eighth
+    /// degree. Its coefficients in the binomial basis are small integers, so
+    /// every value is an integer.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+        let mut lines = Vec::with_capacity(size);
+
+        for _ in 0..size {
+            let coefficients = (0..=rng.gen_range(0..=8))
+                .map(|_| rng.gen_range(-10..=10))
+                .collect::<Vec<i64>>();
+
+            let values = (0..21)
+                .map(|x| {
+                    // sum of c_k * binomial(x, k)
+                    let mut binomial = 1;
+                    let mut value = 0;
+                    for (k, c) in coefficients.iter().enumerate() {
+                        value += c * binomial;
+                        binomial = binomial * (x - k as i64) / (k as i64 + 1);
+                    }
+                    value.to_string()
+                })
+                .collect::<Vec<String>>();
+
+            lines.push(values.join(" "));
+        }
+
+        lines.join("\n")
+    }
 }
 
 #[cfg(test)]
@@ -158,38 +331,165 @@ mod test {
 
     #[test_log::test]
     pub fn test_series_extend_left_by_one() {
-        let mut s = Series::from_initial(vec![0, 3, 6, 9, 12, 15]);
+        let mut s = Series::from_initial(vec![0, 3, 6, 9, 12, 15]).unwrap();
         s.extend_left_by_one();
         assert_eq!(s.initial, vec![-3, 0, 3, 6, 9, 12, 15]);
 
-        let mut s = Series::from_initial(vec![1, 3, 6, 10, 15, 21]);
+        let mut s = Series::from_initial(vec![1, 3, 6, 10, 15, 21]).unwrap();
         s.extend_left_by_one();
         assert_eq!(s.initial, vec![0, 1, 3, 6, 10, 15, 21]);
 
-        let mut s = Series::from_initial(vec![10, 13, 16, 21, 30, 45]);
+        let mut s = Series::from_initial(vec![10, 13, 16, 21, 30, 45]).unwrap();
         s.extend_left_by_one();
         assert_eq!(s.initial, vec![5, 10, 13, 16, 21, 30, 45]);
     }
 
     #[test_log::test]
     pub fn test_series_extend_by_one() {
-        let mut s = Series::from_initial(vec![0, 3, 6, 9, 12, 15]);
+        let mut s = Series::from_initial(vec![0, 3, 6, 9, 12, 15]).unwrap();
         s.extend_by_one();
         assert_eq!(s.initial, vec![0, 3, 6, 9, 12, 15, 18]);
 
-        let mut s = Series::from_initial(vec![1, 3, 6, 10, 15, 21]);
+        let mut s = Series::from_initial(vec![1, 3, 6, 10, 15, 21]).unwrap();
         s.extend_by_one();
         assert_eq!(s.initial, vec![1, 3, 6, 10, 15, 21, 28]);
 
-        let mut s = Series::from_initial(vec![10, 13, 16, 21, 30, 45]);
+        let mut s = Series::from_initial(vec![10, 13, 16, 21, 30, 45]).unwrap();
         s.extend_by_one();
         assert_eq!(s.initial, vec![10, 13, 16, 21, 30, 45, 68]);
     }
 
+    #[test]
+    pub fn test_value_at() {
+        let s = Series::from_initial(vec![10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(
+            s.forward_differences().collect::<Vec<i128>>(),
+            [10, 3, 0, 2, 0]
+        );
+        assert_eq!(s.value_at(6), Ok(68));
+        assert_eq!(s.value_at(-1), Ok(5));
+        assert_eq!(s.value_at(2), Ok(16));
+
+        // the triangular numbers, (n + 1)(n + 2) / 2 at index n
+        let s = Series::from_initial(vec![1, 3, 6, 10, 15, 21]).unwrap();
+        let n: i128 = 1_000_000_000_000_000_000;
+        assert_eq!(s.value_at(n), Ok((n + 1) * (n + 2) / 2));
+        assert_eq!(s.value_at(-n), Ok((1 - n) * (2 - n) / 2));
+
+        let s = Series::from_initial(vec![0, 3, 6, 9, 12, 15]).unwrap();
+        assert_eq!(s.value_at(i128::MAX / 3), Ok(i128::MAX / 3 * 3));
+        assert_eq!(
+            s.value_at(i128::MAX / 2),
+            Err(SeriesError::Overflow {
+                index: i128::MAX / 2
+            })
+        );
+    }
+
+    #[test]
+    pub fn test_shape() {
+        let shape = |initial: Vec<i128>| Series::from_initial(initial).unwrap().shape();
+
+        assert_eq!(
+            shape(vec![10, 13, 16, 21, 30, 45]),
+            Shape::Polynomial {
+                degree: 3,
+                coefficients: vec![10, 3, 0, 2],
+            }
+        );
+        assert_eq!(
+            shape(vec![4, 4, 4]),
+            Shape::Polynomial {
+                degree: 0,
+                coefficients: vec![4],
+            }
+        );
+        assert_eq!(
+            shape(vec![0, 0]),
+            Shape::Polynomial {
+                degree: 0,
+                coefficients: vec![0],
+            }
+        );
+        assert_eq!(shape(vec![1, 2, 4, 8, 16]), Shape::NotRepresentable);
+        assert_eq!(shape(vec![7]), Shape::NotRepresentable);
+    }
+
+    #[test]
+    pub fn test_not_polynomial() {
+        let series = Day09::parse("0 3 6 9 12 15\n1 2 4 8 16\n10 13 16 21 30 45").unwrap();
+
+        assert_eq!(
+            part_one(&series),
+            Err(SeriesError::NotPolynomial { line: 2 })
+        );
+        assert_eq!(
+            Day09::part_two(&series).unwrap_err().to_string(),
+            "line 2 is not the values of a polynomial"
+        );
+    }
+
+    #[test]
+    pub fn test_difference_overflow() {
+        // the differences of the extreme 64 bit values still fit 128 bits
+        let series = Day09::parse("9223372036854775807 -9223372036854775808 0").unwrap();
+        assert_eq!(
+            series[0].forward_differences().collect::<Vec<i128>>(),
+            [i64::MAX as i128, 1 - (1 << 64), 3 * (1 << 63) - 1]
+        );
+        assert_eq!(
+            part_one(&series),
+            Err(SeriesError::NotPolynomial { line: 1 })
+        );
+
+        // alternating extremes double the differences in every row
+        let initial = (0..80)
+            .map(|i| if i % 2 == 0 { i128::MAX } else { i128::MIN })
+            .collect::<Vec<i128>>();
+        assert_eq!(
+            Series::from_initial(initial),
+            Err(SeriesError::DifferenceOverflow { depth: 1, index: 0 })
+        );
+
+        // the first differences fit, the second one from index 1 does not
+        assert_eq!(
+            Series::from_initial(vec![0, 0, i128::MAX, -1]),
+            Err(SeriesError::DifferenceOverflow { depth: 2, index: 1 })
+        );
+
+        let line = (0..80)
+            .map(|i| if i % 2 == 0 { i64::MAX } else { i64::MIN }.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        assert_eq!(
+            Day09::parse(&format!("0 1 2\n{line}")).err(),
+            Some(ParseError::new(2, 1, "differences fitting 128 bits"))
+        );
+    }
+
+    #[test]
+    pub fn test_value_at_generated() {
+        let series = Day09::parse(&Day09::generate(20, 3)).unwrap();
+
+        for s in series {
+            let (mut right, mut left) = (s.clone(), s.clone());
+
+            for i in 0..30 {
+                right.extend_by_one();
+                left.extend_left_by_one();
+
+                let last = *right.initial.last().unwrap();
+                let first = *left.initial.first().unwrap();
+                assert_eq!(s.value_at(21 + i), Ok(last));
+                assert_eq!(s.value_at(-1 - i), Ok(first));
+            }
+        }
+    }
+
     #[test_log::test]
     pub fn test_series_from_initial() {
         assert_eq!(
-            Series::from_initial(vec![0, 3, 6, 9, 12, 15]),
+            Series::from_initial(vec![0, 3, 6, 9, 12, 15]).unwrap(),
             Series {
                 initial: vec![0, 3, 6, 9, 12, 15],
                 extrapolation: vec![vec![3, 3, 3, 3, 3], vec![0, 0, 0, 0]],
@@ -197,7 +497,7 @@ mod test {
         );
 
         assert_eq!(
-            Series::from_initial(vec![1, 3, 6, 10, 15, 21]),
+            Series::from_initial(vec![1, 3, 6, 10, 15, 21]).unwrap(),
             Series {
                 initial: vec![1, 3, 6, 10, 15, 21],
                 extrapolation: vec![vec![2, 3, 4, 5, 6], vec![1, 1, 1, 1], vec![0, 0, 0]],
@@ -205,7 +505,7 @@ mod test {
         );
 
         assert_eq!(
-            Series::from_initial(vec![10, 13, 16, 21, 30, 45]),
+            Series::from_initial(vec![10, 13, 16, 21, 30, 45]).unwrap(),
             Series {
                 initial: vec![10, 13, 16, 21, 30, 45],
                 extrapolation: vec![
@@ -225,8 +525,9 @@ mod test {
                 "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45"
-            ),
-            Ok((
+            )
+            .unwrap(),
+            (
                 "",
                 vec![
                     Series {
@@ -247,33 +548,56 @@ mod test {
                         ],
                     },
                 ]
-            ))
+            )
         );
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        let res = Day09::parse("0 3 6 9\n1 3 x 10");
+
+        assert_eq!(res.err(), Some(ParseError::new(2, 5, "integer")));
+    }
+
     #[test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             "0 3 6 9 12 15".to_string(),
             "1 3 6 10 15 21".to_string(),
             "10 13 16 21 30 45".to_string(),
         ];
 
-        let res = part_one(input);
+        let series = Day09::parse(&input.join("\n")).unwrap();
+
+        let res = part_one(&series);
 
-        assert_eq!(res, 114);
+        assert_eq!(res, Ok(114));
     }
 
     #[test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "0 3 6 9 12 15".to_string(),
             "1 3 6 10 15 21".to_string(),
             "10 13 16 21 30 45".to_string(),
         ];
 
-        let res = part_two(input);
+        let series = Day09::parse(&input.join("\n")).unwrap();
 
-        assert_eq!(res, 2);
+        let res = part_two(&series);
+
+        assert_eq!(res, Ok(2));
+    }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day09::generate(50, 1);
+        assert_eq!(input, Day09::generate(50, 1));
+
+        let series = Day09::parse(&input).unwrap();
+        assert_eq!(series.len(), 50);
+        assert!(series
+            .iter()
+            .all(|s| s.initial.len() == 21 && s.degree().is_some_and(|d| d <= 8)));
     }
 }
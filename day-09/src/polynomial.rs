@@ -0,0 +1,338 @@
+use std::fmt;
+
+use crate::{gcd, Series};
+
+/// An exact fraction, kept reduced with a positive denominator. The
+/// arithmetic is checked and returns `None` on overflow.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Rational = Rational {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// `None` for a zero denominator or when reducing overflows.
+    pub fn new(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+
+        // reduced on the magnitudes, which also hold the one of `i128::MIN`
+        let divisor = gcd(numerator, denominator);
+        let magnitude = numerator.unsigned_abs() / divisor;
+        let numerator = if (numerator < 0) != (denominator < 0) {
+            0i128.checked_sub_unsigned(magnitude)?
+        } else {
+            i128::try_from(magnitude).ok()?
+        };
+
+        Some(Self {
+            numerator,
+            denominator: i128::try_from(denominator.unsigned_abs() / divisor).ok()?,
+        })
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        // denominators are positive, so their divisors fit
+        let divisor = gcd(self.denominator, other.denominator) as i128;
+        let (a, b) = (self.denominator / divisor, other.denominator / divisor);
+
+        Self::new(
+            self.numerator
+                .checked_mul(b)?
+                .checked_add(other.numerator.checked_mul(a)?)?,
+            self.denominator.checked_mul(b)?,
+        )
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // cross-reduce first so the products stay small, by divisors of the
+        // positive denominators, which fit
+        let a = gcd(self.numerator, other.denominator) as i128;
+        let b = gcd(other.numerator, self.denominator) as i128;
+
+        Self::new(
+            (self.numerator / a).checked_mul(other.numerator / b)?,
+            (self.denominator / b).checked_mul(other.denominator / a)?,
+        )
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// A polynomial with exact coefficients, `coefficients[k]` being the factor
+/// of `x^k`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Polynomial {
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    /// Drops the zero coefficients of the highest powers.
+    pub fn new(mut coefficients: Vec<Rational>) -> Self {
+        while coefficients.last() == Some(&Rational::ZERO) {
+            coefficients.pop();
+        }
+
+        Self { coefficients }
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// The value at `x` by Horner's scheme, `None` on overflow.
+    pub fn eval(&self, x: Rational) -> Option<Rational> {
+        self.coefficients
+            .iter()
+            .rev()
+            .try_fold(Rational::ZERO, |acc, c| acc.checked_mul(x)?.checked_add(*c))
+    }
+
+    /// `binomial(x, k)` as a polynomial in `x`, `None` on overflow.
+    pub fn binomial(k: usize) -> Option<Self> {
+        let mut coefficients = vec![Rational::ONE];
+
+        // multiply by (x - i) / (i + 1) for every i below k
+        for i in 0..k as i128 {
+            let shift = Rational::new(-i, i + 1)?;
+            let scale = Rational::new(1, i + 1)?;
+            let mut next = vec![Rational::ZERO; coefficients.len() + 1];
+
+            for (power, c) in coefficients.iter().enumerate() {
+                next[power + 1] = next[power + 1].checked_add(c.checked_mul(scale)?)?;
+                next[power] = next[power].checked_add(c.checked_mul(shift)?)?;
+            }
+            coefficients = next;
+        }
+
+        Some(Self::new(coefficients))
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| **c != Rational::ZERO);
+
+        let mut first = true;
+        for (power, c) in terms {
+            let negative = c.numerator() < 0;
+            // unsigned, as the magnitude of `i128::MIN` does not fit `i128`
+            let numerator = c.numerator().unsigned_abs();
+            let magnitude = if c.is_integer() {
+                numerator.to_string()
+            } else {
+                format!("{numerator}/{}", c.denominator())
+            };
+
+            match (first, negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+
+            match (power, c.is_integer()) {
+                (0, _) => write!(f, "{magnitude}")?,
+                (_, true) if numerator == 1 => {}
+                (_, true) => write!(f, "{magnitude}")?,
+                (_, false) => write!(f, "({magnitude})")?,
+            }
+
+            match power {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{power}")?,
+            }
+        }
+
+        if first {
+            write!(f, "0")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Series {
+    /// The polynomial of the lowest degree going through all values, with
+    /// the first value at `x = 0`, from the forward differences.
+    /// `None` when a coefficient overflows.
+    pub fn polynomial(&self) -> Option<Polynomial> {
+        let mut coefficients = vec![];
+
+        for (k, difference) in self.forward_differences().enumerate() {
+            if difference == 0 {
+                continue;
+            }
+
+            let difference = Rational::from(difference);
+            let basis = Polynomial::binomial(k)?;
+
+            coefficients.resize(coefficients.len().max(k + 1), Rational::ZERO);
+            for (power, c) in basis.coefficients().iter().enumerate() {
+                coefficients[power] =
+                    coefficients[power].checked_add(c.checked_mul(difference)?)?;
+            }
+        }
+
+        Some(Polynomial::new(coefficients))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    pub fn test_rational() {
+        let half = Rational::new(2, -4).unwrap();
+
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!(half.to_string(), "-1/2");
+        assert_eq!(
+            half.checked_add(Rational::new(5, 6).unwrap()),
+            Rational::new(1, 3)
+        );
+        assert_eq!(
+            half.checked_mul(Rational::from(-4)),
+            Some(Rational::from(2))
+        );
+        assert_eq!(Rational::new(1, 0), None);
+        assert_eq!(Rational::from(i128::MAX).checked_add(Rational::ONE), None);
+
+        // the magnitude of `i128::MIN` does not fit, but reducing still works
+        assert_eq!(Rational::new(i128::MIN, 1), Some(Rational::from(i128::MIN)));
+        assert_eq!(Rational::new(i128::MIN, i128::MIN), Some(Rational::ONE));
+        assert_eq!(Rational::new(0, i128::MIN), Some(Rational::ZERO));
+        assert_eq!(Rational::new(i128::MIN, 2), Rational::new(i128::MIN / 2, 1));
+        assert_eq!(Rational::new(i128::MIN, -1), None);
+        assert_eq!(Rational::new(1, i128::MIN), None);
+    }
+
+    #[test]
+    pub fn test_polynomial() {
+        let s = Series::from_initial(vec![10, 13, 16, 21, 30, 45]).unwrap();
+        let p = s.polynomial().unwrap();
+
+        assert_eq!(p.degree(), Some(3));
+        assert_eq!(p.to_string(), "(1/3)x^3 - x^2 + (11/3)x + 10");
+        assert_eq!(p.eval(Rational::from(6)), Some(Rational::from(68)));
+        assert_eq!(p.eval(Rational::from(-1)), Some(Rational::from(5)));
+        assert_eq!(p.eval(Rational::new(1, 2).unwrap()), Rational::new(93, 8));
+
+        let p = Series::from_initial(vec![-3, -1, 1])
+            .unwrap()
+            .polynomial()
+            .unwrap();
+        assert_eq!(p.to_string(), "2x - 3");
+
+        let p = Series::from_initial(vec![0, 0, 0])
+            .unwrap()
+            .polynomial()
+            .unwrap();
+        assert_eq!((p.degree(), p.to_string()), (None, "0".to_string()));
+
+        // not a polynomial series, but still interpolated through its values
+        let p = Series::from_initial(vec![1, 2, 4, 8])
+            .unwrap()
+            .polynomial()
+            .unwrap();
+        assert_eq!(p.to_string(), "(1/6)x^3 + (5/6)x + 1");
+
+        let p = Polynomial::new(vec![Rational::from(i128::MIN), Rational::from(i128::MIN)]);
+        assert_eq!(
+            p.to_string(),
+            "-170141183460469231731687303715884105728x - 170141183460469231731687303715884105728"
+        );
+    }
+
+    #[test]
+    pub fn test_polynomial_generated() {
+        for seed in 0..10 {
+            let series = Day09::parse(&Day09::generate(20, seed)).unwrap();
+
+            for s in series {
+                let p = s.polynomial().unwrap();
+                let at = |x: i128| p.eval(Rational::from(x)).unwrap();
+
+                assert!(p.degree().unwrap_or(0) <= 8);
+                for (x, v) in s.initial.iter().enumerate() {
+                    assert_eq!(at(x as i128), Rational::from(*v));
+                }
+
+                let (mut right, mut left) = (s.clone(), s.clone());
+                for i in 0..10 {
+                    right.extend_by_one();
+                    left.extend_left_by_one();
+
+                    let last = *right.initial.last().unwrap();
+                    let first = *left.initial.first().unwrap();
+                    assert_eq!(at(21 + i), Rational::from(last));
+                    assert_eq!(at(-1 - i), Rational::from(first));
+                    assert_eq!(s.value_at(21 + i).map(Rational::from), Ok(at(21 + i)));
+                }
+            }
+        }
+    }
+}
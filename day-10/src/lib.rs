@@ -1,211 +1,73 @@
-use std::i32;
+mod render;
 
+use aoc_common::{
+    seeded_rng, Answer, Direction, Generator, Grid, ParseError, Point, Solution, SolveError,
+};
+use rand::Rng;
 use tracing::trace;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
+pub use render::*;
 
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
-
-#[derive(Debug, PartialEq, Clone)]
-pub struct Position {
-    pub x: usize,
-    pub y: usize,
-}
-
-#[derive(Debug, PartialEq)]
-pub struct Offset {
-    pub x: i32,
-    pub y: i32,
-}
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-pub enum Directions {
-    North,
-    East,
-    South,
-    West,
+/// The maze with its start tile and the loop through it, both found once
+/// while parsing.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Maze {
+    pub grid: Grid<char>,
+    pub start: Point,
+    /// The tiles of the loop, from the start around back to it.
+    pub main_loop: Vec<Point>,
 }
 
-fn directions_from_symbol(c: char) -> Vec<Directions> {
+fn directions_from_symbol(c: char) -> Vec<Direction> {
     match c {
-        '|' => vec![Directions::North, Directions::South],
-        '-' => vec![Directions::East, Directions::West],
-        'J' => vec![Directions::North, Directions::West],
-        'L' => vec![Directions::North, Directions::East],
-        '7' => vec![Directions::South, Directions::West],
-        'F' => vec![Directions::South, Directions::East],
-        'S' => vec![
-            Directions::North,
-            Directions::East,
-            Directions::South,
-            Directions::West,
-        ],
+        '|' => vec![Direction::North, Direction::South],
+        '-' => vec![Direction::East, Direction::West],
+        'J' => vec![Direction::North, Direction::West],
+        'L' => vec![Direction::North, Direction::East],
+        '7' => vec![Direction::South, Direction::West],
+        'F' => vec![Direction::South, Direction::East],
+        'S' => Direction::ALL.to_vec(),
         _ => vec![],
     }
 }
 
 #[tracing::instrument(skip(maze))]
-fn find_start_position(maze: &[Vec<char>]) -> Option<Position> {
-    for (y, row) in maze.iter().enumerate() {
-        match row.iter().position(|c| *c == 'S') {
-            Some(x) => return Some(Position { x, y }),
-            None => continue,
-        };
-    }
-
-    None
-}
-
-fn parse_maze(inp: Vec<String>) -> Vec<Vec<char>> {
-    inp.iter()
-        .map(|r| {
-            let o = r.as_bytes();
-            let o: Vec<char> = o.iter().map(|c| *c as char).collect();
-            o
-        })
-        .collect()
-}
-
-#[tracing::instrument]
-fn is_connected(a: Vec<Directions>, a_pos: Position, b: Vec<Directions>, b_pos: Position) -> bool {
-    for dir in a {
-        if dir == Directions::North
-            && b.contains(&Directions::South)
-            && a_pos.x == b_pos.x
-            && a_pos.y > b_pos.y
-        {
-            trace!("n 2 s");
-            return true;
-        }
-
-        if dir == Directions::South
-            && b.contains(&Directions::North)
-            && a_pos.x == b_pos.x
-            && a_pos.y < b_pos.y
-        {
-            trace!("s 2 n");
-            return true;
-        }
-
-        if dir == Directions::West
-            && b.contains(&Directions::East)
-            && a_pos.y == b_pos.y
-            && a_pos.x > b_pos.x
-        {
-            trace!("w 2 e");
-            return true;
-        }
-
-        if dir == Directions::East
-            && b.contains(&Directions::West)
-            && a_pos.y == b_pos.y
-            && a_pos.x < b_pos.x
-        {
-            trace!("e 2 w");
-            return true;
-        }
-    }
-
-    return false;
+fn find_start_position(maze: &Grid<char>) -> Option<Point> {
+    maze.position(|c| *c == 'S')
 }
 
+#[tracing::instrument(skip(maze))]
 fn position_is_connected(
-    maze: &[Vec<char>],
-    pos: Position,
-    offset: Offset,
-    prev: Position,
-) -> Option<Position> {
-    let directions = directions_from_symbol(maze[pos.y][pos.x]);
-
-    let pos_x = pos.x as i32 + offset.x;
-    let pos_y = pos.y as i32 + offset.y;
-
-    if pos_y < 0 || pos_y >= maze.len() as i32 {
-        return None;
-    }
-
-    let pos_y = pos_y as usize;
-
-    if pos_x < 0 || pos_x > maze[pos_y].len() as i32 {
+    maze: &Grid<char>,
+    pos: Point,
+    direction: Direction,
+    prev: Point,
+) -> Option<Point> {
+    let next = maze.step(pos, direction)?;
+
+    if next == prev {
         return None;
     }
 
-    let pos_x = pos_x as usize;
-
-    if pos_x == prev.x && pos_y == prev.y {
-        return None;
-    }
+    trace!("{:?} {:?}", next, directions_from_symbol(maze[next]));
 
-    trace!(
-        "{} {} {:?}",
-        pos_x,
-        pos_y,
-        directions_from_symbol(maze[pos_y][pos_x])
-    );
-    let candidate_dirs = directions_from_symbol(maze[pos_y][pos_x]);
-
-    if !is_connected(
-        directions.clone(),
-        pos.clone(),
-        candidate_dirs,
-        Position { x: pos_x, y: pos_y },
-    ) {
-        return None;
-    }
+    let connected = directions_from_symbol(maze[pos]).contains(&direction)
+        && directions_from_symbol(maze[next]).contains(&direction.opposite());
 
-    Some(Position { x: pos_x, y: pos_y })
+    connected.then_some(next)
 }
 
-fn adjacent_connected_pipes(maze: &[Vec<char>], pos: Position, prev: Position) -> Option<Position> {
-    let around = vec![
-        Offset { x: 0, y: 1 },
-        Offset { x: -1, y: 0 },
-        Offset { x: 0, y: -1 },
-        Offset { x: 1, y: 0 },
+fn adjacent_connected_pipes(maze: &Grid<char>, pos: Point, prev: Point) -> Option<Point> {
+    let around = [
+        Direction::South,
+        Direction::West,
+        Direction::North,
+        Direction::East,
     ];
 
-    for offset in around {
-        match position_is_connected(maze, pos.clone(), offset, prev.clone()) {
-            Some(p) => return Some(p),
-            None => continue,
-        }
-    }
-
-    None
-}
-
-#[tracing::instrument(skip_all)]
-fn find_loop_length(maze: &[Vec<char>], start_pos: Position) -> usize {
-    let mut prev = start_pos.clone();
-    let mut player = start_pos;
-    let mut counter = 0;
-    loop {
-        let new_player = match adjacent_connected_pipes(maze, player.clone(), prev.clone()) {
-            Some(p) => p.clone(),
-            None => todo!("no further way"),
-        };
-
-        prev = player;
-        player = new_player;
-
-        trace!(?player);
-
-        counter += 1;
-
-        if maze[player.y][player.x] == 'S' {
-            break;
-        }
-    }
-
-    trace!(counter);
-    counter
+    around
+        .into_iter()
+        .find_map(|direction| position_is_connected(maze, pos, direction, prev))
 }
 
 pub fn is_char_with_side(c: char) -> bool {
@@ -213,14 +75,14 @@ pub fn is_char_with_side(c: char) -> bool {
 }
 
 #[tracing::instrument(skip(main_loop))]
-pub fn find_tile_count_in_row(row: &str, y: usize, main_loop: &[Position]) -> usize {
+pub fn find_tile_count_in_row(row: &str, y: usize, main_loop: &[Point]) -> usize {
     let mut inside_loop = false;
 
     let count = row
         .chars()
         .enumerate()
         .filter(|(x, c)| {
-            if main_loop.contains(&Position { x: *x, y }) {
+            if main_loop.contains(&Point { x: *x, y }) {
                 if is_char_with_side(*c) {
                     inside_loop = !inside_loop;
                 }
@@ -237,138 +99,252 @@ pub fn find_tile_count_in_row(row: &str, y: usize, main_loop: &[Position]) -> us
     count
 }
 
-pub fn find_main_loop(maze: &[Vec<char>], start_pos: Position) -> Vec<Position> {
-    let mut prev = start_pos.clone();
-    let mut player = start_pos.clone();
+/// Walks the loop starting and ending at `start_pos`. Fails with the
+/// position of the pipe that leads into a dead end.
+pub fn find_main_loop(maze: &Grid<char>, start_pos: Point) -> Result<Vec<Point>, ParseError> {
+    let mut prev = start_pos;
+    let mut player = start_pos;
     let mut output = vec![start_pos];
     loop {
-        let new_player = match adjacent_connected_pipes(maze, player.clone(), prev.clone()) {
-            Some(p) => p.clone(),
-            None => todo!("no further way"),
+        let new_player = match adjacent_connected_pipes(maze, player, prev) {
+            Some(p) => p,
+            None => {
+                return Err(ParseError::new(
+                    player.y + 1,
+                    player.x + 1,
+                    "pipe connecting back to the start",
+                ))
+            }
         };
 
         prev = player;
         player = new_player;
 
-        output.push(player.clone());
+        output.push(player);
 
-        if maze[player.y][player.x] == 'S' {
+        if maze[player] == 'S' {
             break;
         }
     }
 
-    output
+    trace!(length = output.len() - 1);
+    Ok(output)
 }
 
-fn get_char_for_start_pos(maze: &[Vec<char>], pos: Position) -> char {
-    let around = vec![
-        (Offset { x: 0, y: 1 }, Directions::South),
-        (Offset { x: -1, y: 0 }, Directions::West),
-        (Offset { x: 0, y: -1 }, Directions::North),
-        (Offset { x: 1, y: 0 }, Directions::East),
-    ];
+fn get_char_for_start_pos(maze: &Grid<char>, pos: Point) -> char {
+    let con_dirs: Vec<Direction> = Direction::ALL
+        .into_iter()
+        .filter(|d| position_is_connected(maze, pos, *d, pos).is_some())
+        .collect();
 
-    let mut con_dirs = vec![];
-    for (offset, con_dir) in around {
-        match position_is_connected(maze, pos.clone(), offset, Position { x: 0, y: 0 }) {
-            Some(_) => con_dirs.push(con_dir),
-            None => continue,
-        }
-    }
+    symbol_from_directions(&con_dirs)
+}
 
-    match con_dirs.as_slice() {
-        [Directions::North, Directions::South] => '|',
-        [Directions::East, Directions::West] => '-',
-        [Directions::North, Directions::West] => 'J',
-        [Directions::North, Directions::East] => 'L',
-        [Directions::South, Directions::West] => '7',
-        [Directions::South, Directions::East] => 'F',
-        &[] | &[_] | &[_, ..] => 'S',
+/// The pipe connecting the sorted `directions`, the inverse of
+/// [`directions_from_symbol`].
+fn symbol_from_directions(directions: &[Direction]) -> char {
+    match directions {
+        [Direction::North, Direction::South] => '|',
+        [Direction::East, Direction::West] => '-',
+        [Direction::North, Direction::West] => 'J',
+        [Direction::North, Direction::East] => 'L',
+        [Direction::South, Direction::West] => '7',
+        [Direction::East, Direction::South] => 'F',
+        _ => 'S',
     }
 }
 
 #[tracing::instrument(skip_all)]
-pub fn part_one(inp: Vec<String>) -> usize {
-    let maze = parse_maze(inp);
-    let maze = maze.as_slice();
+pub fn part_one(maze: &Maze) -> usize {
+    let loop_length = maze.main_loop.len() - 1;
 
-    let start_pos = match find_start_position(maze) {
-        Some(p) => p,
-        None => todo!("no start position"),
-    };
-
-    let loop_length = find_loop_length(maze, start_pos);
-
-    if loop_length % 2 == 0 {
+    if loop_length.is_multiple_of(2) {
         return loop_length / 2;
     }
 
     (loop_length / 2) + 1
 }
 
-pub fn part_two(inp: Vec<String>) -> usize {
-    let maze = parse_maze(inp.clone());
-    let maze = maze.as_slice();
-
-    let start_pos = match find_start_position(maze) {
-        Some(p) => p,
-        None => todo!("no start position"),
-    };
+/// How to count the tiles enclosed by the loop.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Enclosure {
+    /// Walks every row, toggling inside and outside on crossing the loop.
+    Scanline,
+    /// Takes the area of the loop polygon and subtracts its boundary with
+    /// Pick's theorem, looking at the loop only.
+    Shoelace,
+}
 
-    let main_loop = find_main_loop(maze, start_pos.clone());
+pub fn scanline_tile_count(maze: &Grid<char>, main_loop: &[Point]) -> usize {
+    let start_pos = main_loop[0];
     let start_symbol = get_char_for_start_pos(maze, start_pos);
 
-    inp.iter()
+    maze.rows()
         .map(|row| {
-            if row.contains('S') {
-                return row.replace('S', &start_symbol.to_string());
-            }
-            row.to_string()
+            row.iter()
+                .map(|c| match c {
+                    'S' => start_symbol,
+                    _ => *c,
+                })
+                .collect::<String>()
         })
         .enumerate()
-        .map(|(y, row)| find_tile_count_in_row(&row, y, &main_loop))
+        .map(|(y, row)| find_tile_count_in_row(&row, y, main_loop))
         .sum()
 }
 
+/// The tiles inside the closed `main_loop`, which starts and ends on the
+/// same tile. The shoelace formula gives twice the area of the polygon
+/// through the tile centres, and Pick's theorem `A = I + B / 2 - 1` the
+/// number `I` of tiles inside it from its `B` boundary tiles.
+pub fn shoelace_tile_count(main_loop: &[Point]) -> usize {
+    let double_area = main_loop
+        .windows(2)
+        .map(|w| (w[0].x * w[1].y) as i64 - (w[1].x * w[0].y) as i64)
+        .sum::<i64>()
+        .unsigned_abs() as usize;
+    let boundary = main_loop.len() - 1;
+
+    (double_area + 2 - boundary) / 2
+}
+
+pub fn enclosed_tile_count(maze: &Maze, enclosure: Enclosure) -> usize {
+    match enclosure {
+        Enclosure::Scanline => scanline_tile_count(&maze.grid, &maze.main_loop),
+        Enclosure::Shoelace => shoelace_tile_count(&maze.main_loop),
+    }
+}
+
+pub fn part_two(maze: &Maze) -> usize {
+    enclosed_tile_count(maze, Enclosure::Shoelace)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Maze;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let grid = Grid::parse_with(
+            input,
+            |c| "|-LJ7F.S".contains(c).then_some(c),
+            "pipe `|`, `-`, `L`, `J`, `7`, `F`, ground `.` or start `S`",
+        )?;
+
+        let start = find_start_position(&grid)
+            .ok_or_else(|| ParseError::at(input, &input[input.len()..], "start tile `S`"))?;
+        let main_loop = find_main_loop(&grid, start)?;
+
+        Ok(Maze {
+            grid,
+            start,
+            main_loop,
+        })
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day10 {
+    /// A `size` by `size` maze, rounded down to an even width. The loop runs
+    /// along the border and dips into the maze from the top in teeth of
+    /// random depth. Every other tile is a random pipe or ground.
+    fn generate(size: usize, seed: u64) -> String {
+        const JUNK: &[u8] = b"|-LJ7F....";
+
+        let mut rng = seeded_rng(seed);
+        let (width, height) = (size.max(4) & !1, size.max(4));
+
+        let mut main_loop = vec![];
+        main_loop.extend((0..height).map(|y| Point::new(0, y)));
+        main_loop.extend((1..width).map(|x| Point::new(x, height - 1)));
+        main_loop.extend((0..height - 1).rev().map(|y| Point::new(width - 1, y)));
+        for x in (2..width - 1).step_by(2).rev() {
+            let depth = rng.gen_range(0..height - 2);
+            main_loop.extend((0..=depth).map(|y| Point::new(x, y)));
+            main_loop.extend((0..=depth).rev().map(|y| Point::new(x - 1, y)));
+        }
+
+        let mut maze = Grid::filled(width, height, '.');
+        for p in maze.points().collect::<Vec<Point>>() {
+            maze[p] = char::from(JUNK[rng.gen_range(0..JUNK.len())]);
+        }
+
+        let n = main_loop.len();
+        for (i, pos) in main_loop.iter().enumerate() {
+            let mut directions = [main_loop[(i + n - 1) % n], main_loop[(i + 1) % n]].map(|next| {
+                Direction::ALL
+                    .into_iter()
+                    .find(|d| maze.step(*pos, *d) == Some(next))
+                    .unwrap()
+            });
+            directions.sort();
+
+            maze[*pos] = symbol_from_directions(&directions);
+        }
+
+        // junk pipes next to the start must not look connected to it
+        let start = main_loop[rng.gen_range(0..n)];
+        for next in maze.neighbours(start).collect::<Vec<Point>>() {
+            if !main_loop.contains(&next) {
+                maze[next] = '.';
+            }
+        }
+        maze[start] = 'S';
+
+        maze.to_string()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
 
     #[test_log::test]
     pub fn test_get_char_for_start_pos() {
-        let maze = parse_maze(vec![
-            "..........".to_string(),
-            ".S------7.".to_string(),
-            ".|F----7|.".to_string(),
-            ".||....||.".to_string(),
-            ".||....||.".to_string(),
-            ".|L-7F-J|.".to_string(),
-            ".|..||..|.".to_string(),
-            ".L--JL--J.".to_string(),
-            "..........".to_string(),
-        ]);
-        let maze = maze.as_slice();
-        let start_pos = Position { x: 1, y: 1 };
-
-        assert_eq!(get_char_for_start_pos(maze, start_pos), 'F');
+        let maze: Grid<char> = [
+            "..........",
+            ".S------7.",
+            ".|F----7|.",
+            ".||....||.",
+            ".||....||.",
+            ".|L-7F-J|.",
+            ".|..||..|.",
+            ".L--JL--J.",
+            "..........",
+        ]
+        .join("\n")
+        .parse()
+        .unwrap();
+        let start_pos = Point { x: 1, y: 1 };
+
+        assert_eq!(get_char_for_start_pos(&maze, start_pos), 'F');
     }
 
     #[test_log::test]
     pub fn test_find_tile_count_in_row() {
-        let maze = parse_maze(vec![
-            "..........".to_string(),
-            ".S------7.".to_string(),
-            ".|F----7|.".to_string(),
-            ".||....||.".to_string(),
-            ".||....||.".to_string(),
-            ".|L-7F-J|.".to_string(),
-            ".|..||..|.".to_string(),
-            ".L--JL--J.".to_string(),
-            "..........".to_string(),
-        ]);
-        let maze = maze.as_slice();
-
-        let main_loop = find_main_loop(maze, Position { x: 1, y: 1 });
+        let maze: Grid<char> = [
+            "..........",
+            ".S------7.",
+            ".|F----7|.",
+            ".||....||.",
+            ".||....||.",
+            ".|L-7F-J|.",
+            ".|..||..|.",
+            ".L--JL--J.",
+            "..........",
+        ]
+        .join("\n")
+        .parse()
+        .unwrap();
+
+        let main_loop = find_main_loop(&maze, Point { x: 1, y: 1 }).unwrap();
         let main_loop = main_loop.as_slice();
 
         assert_eq!(find_tile_count_in_row("..........", 0, main_loop), 0);
@@ -384,35 +360,61 @@ mod test {
 
     #[test_log::test]
     pub fn test_adjacent_connected_pipes() {
-        let maze = &[
+        let maze = &Grid::from_rows(vec![
             vec!['.', '.', '.', '.', '.'],
             vec!['.', 'S', '-', '7', '.'],
             vec!['.', '|', '.', '|', '.'],
             vec!['.', 'L', '-', 'J', '.'],
             vec!['.', '.', '.', '.', '.'],
-        ];
+        ])
+        .unwrap();
 
-        let res = adjacent_connected_pipes(maze, Position { x: 1, y: 1 }, Position { x: 0, y: 0 });
+        let res = adjacent_connected_pipes(maze, Point { x: 1, y: 1 }, Point { x: 0, y: 0 });
 
-        assert_eq!(res, Some(Position { x: 1, y: 2 }));
+        assert_eq!(res, Some(Point { x: 1, y: 2 }));
     }
 
     #[test_log::test]
     pub fn test_find_start_position() {
-        let pos = find_start_position(&[
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', 'S', '-', '7', '.'],
-            vec!['.', '|', '.', '|', '.'],
-            vec!['.', 'L', '-', 'J', '.'],
-            vec!['.', '.', '.', '.', '.'],
-        ]);
+        let pos = find_start_position(
+            &Grid::from_rows(vec![
+                vec!['.', '.', '.', '.', '.'],
+                vec!['.', 'S', '-', '7', '.'],
+                vec!['.', '|', '.', '|', '.'],
+                vec!['.', 'L', '-', 'J', '.'],
+                vec!['.', '.', '.', '.', '.'],
+            ])
+            .unwrap(),
+        );
+
+        assert_eq!(pos, Some(Point { x: 1, y: 1 }));
+    }
 
-        assert_eq!(pos, Some(Position { x: 1, y: 1 }));
+    #[test_log::test]
+    pub fn test_parse_error() {
+        let res = Day10::parse(".....\n.S-7.\n.|.X.");
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(
+                3,
+                4,
+                "pipe `|`, `-`, `L`, `J`, `7`, `F`, ground `.` or start `S`"
+            ))
+        );
+
+        let res = Day10::parse(".....\n.F-7.\n.L-J.");
+        assert_eq!(res.err(), Some(ParseError::new(3, 6, "start tile `S`")));
+
+        let res = Day10::parse(".....\n.S-7.\n.|...\n.L-J.");
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(4, 4, "pipe connecting back to the start"))
+        );
     }
 
     #[test_log::test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             ".....".to_string(),
             ".S-7.".to_string(),
             ".|.|.".to_string(),
@@ -420,11 +422,11 @@ mod test {
             ".....".to_string(),
         ];
 
-        let res = part_one(input);
+        let res = part_one(&Day10::parse(&input.join("\n")).unwrap());
 
         assert_eq!(res, 4);
 
-        let input = vec![
+        let input = [
             "..F7.".to_string(),
             ".FJ|.".to_string(),
             "SJ.L7".to_string(),
@@ -432,14 +434,14 @@ mod test {
             "LJ...".to_string(),
         ];
 
-        let res = part_one(input);
+        let res = part_one(&Day10::parse(&input.join("\n")).unwrap());
 
         assert_eq!(res, 8);
     }
 
     #[test_log::test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "...........".to_string(),
             ".S-------7.".to_string(),
             ".|F-----7|.".to_string(),
@@ -451,9 +453,9 @@ mod test {
             "...........".to_string(),
         ];
 
-        assert_eq!(part_two(input), 4);
+        assert_eq!(part_two(&Day10::parse(&input.join("\n")).unwrap()), 4);
 
-        let input = vec![
+        let input = [
             ".F----7F7F7F7F-7....".to_string(),
             ".|F--7||||||||FJ....".to_string(),
             ".||.FJ||||||||L7....".to_string(),
@@ -466,9 +468,9 @@ mod test {
             "....L---J.LJ.LJLJ...".to_string(),
         ];
 
-        assert_eq!(part_two(input), 8);
+        assert_eq!(part_two(&Day10::parse(&input.join("\n")).unwrap()), 8);
 
-        let input = vec![
+        let input = [
             "FF7FSF7F7F7F7F7F---7".to_string(),
             "L|LJ||||||||||||F--J".to_string(),
             "FL-7LJLJ||||||LJL-77".to_string(),
@@ -481,6 +483,48 @@ mod test {
             "L7JLJL-JLJLJL--JLJ.L".to_string(),
         ];
 
-        assert_eq!(part_two(input), 10);
+        assert_eq!(part_two(&Day10::parse(&input.join("\n")).unwrap()), 10);
+    }
+
+    #[test]
+    pub fn test_enclosure_methods_agree() {
+        let input = [
+            "..........",
+            ".S------7.",
+            ".|F----7|.",
+            ".||....||.",
+            ".||....||.",
+            ".|L-7F-J|.",
+            ".|..||..|.",
+            ".L--JL--J.",
+            "..........",
+        ];
+        let maze = Day10::parse(&input.join("\n")).unwrap();
+
+        assert_eq!(enclosed_tile_count(&maze, Enclosure::Scanline), 4);
+        assert_eq!(enclosed_tile_count(&maze, Enclosure::Shoelace), 4);
+
+        for seed in 0..20 {
+            let maze = Day10::parse(&Day10::generate(24, seed)).unwrap();
+
+            assert_eq!(
+                enclosed_tile_count(&maze, Enclosure::Shoelace),
+                enclosed_tile_count(&maze, Enclosure::Scanline)
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day10::generate(30, 1);
+        assert_eq!(input, Day10::generate(30, 1));
+
+        let maze = Day10::parse(&input).unwrap();
+        assert_eq!((maze.grid.width(), maze.grid.height()), (30, 30));
+        assert_eq!(maze.grid[maze.start], 'S');
+        assert_eq!(maze.main_loop.first(), Some(&maze.start));
+        assert_eq!(maze.main_loop.last(), Some(&maze.start));
+        assert!(part_one(&maze) >= 58);
+        assert!(part_two(&maze) > 0);
     }
 }
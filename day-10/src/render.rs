@@ -0,0 +1,161 @@
+use aoc_common::{Grid, Point};
+
+use crate::{get_char_for_start_pos, is_char_with_side, Maze};
+
+/// Whether [`render`] colours the tiles.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Colours {
+    /// Only characters, for snapshots. Tiles inside the loop show as `I`.
+    Plain,
+    /// ANSI escapes: the loop in bold yellow, the start in bold red, inside
+    /// tiles on green and outside tiles dimmed.
+    Ansi,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Tile {
+    Start,
+    Loop,
+    Inside,
+    Outside,
+}
+
+impl Tile {
+    fn escape(self) -> &'static str {
+        match self {
+            Self::Start => "\x1b[1;31m",
+            Self::Loop => "\x1b[1;33m",
+            Self::Inside => "\x1b[42m",
+            Self::Outside => "\x1b[2m",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+/// The box-drawing character of a pipe, heavy for the loop.
+fn box_drawing(c: char, heavy: bool) -> char {
+    match (c, heavy) {
+        ('|', false) => '│',
+        ('-', false) => '─',
+        ('L', false) => '└',
+        ('J', false) => '┘',
+        ('7', false) => '┐',
+        ('F', false) => '┌',
+        ('|', true) => '┃',
+        ('-', true) => '━',
+        ('L', true) => '┗',
+        ('J', true) => '┛',
+        ('7', true) => '┓',
+        ('F', true) => '┏',
+        ('.', _) => '·',
+        (c, _) => c,
+    }
+}
+
+/// Draws the maze with box-drawing characters, the loop heavy and the start
+/// as the pipe it stands for.
+pub fn render(maze: &Maze, colours: Colours) -> String {
+    let grid = &maze.grid;
+    let start_symbol = get_char_for_start_pos(grid, maze.start);
+
+    let mut on_loop = Grid::filled(grid.width(), grid.height(), false);
+    for p in &maze.main_loop {
+        on_loop[*p] = true;
+    }
+
+    let mut output = String::new();
+    for y in 0..grid.height() {
+        let mut inside = false;
+        let mut current = None;
+
+        for x in 0..grid.width() {
+            let p = Point { x, y };
+            let c = match grid[p] {
+                'S' => start_symbol,
+                c => c,
+            };
+
+            let (tile, shown) = if on_loop[p] {
+                if is_char_with_side(c) {
+                    inside = !inside;
+                }
+
+                let tile = if p == maze.start {
+                    Tile::Start
+                } else {
+                    Tile::Loop
+                };
+                (tile, box_drawing(c, true))
+            } else if inside {
+                let shown = match colours {
+                    Colours::Plain => 'I',
+                    Colours::Ansi => box_drawing(c, false),
+                };
+                (Tile::Inside, shown)
+            } else {
+                (Tile::Outside, box_drawing(c, false))
+            };
+
+            // only switch colours between tiles that differ
+            if colours == Colours::Ansi && current != Some(tile) {
+                if current.is_some() {
+                    output.push_str(RESET);
+                }
+                output.push_str(tile.escape());
+                current = Some(tile);
+            }
+            output.push(shown);
+        }
+
+        if current.is_some() {
+            output.push_str(RESET);
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    pub fn test_render_plain() {
+        let input = ["-L|F7", "7S-7|", "L|7||", "-L-J|", "L|-JF"];
+        let maze = Day10::parse(&input.join("\n")).unwrap();
+
+        assert_eq!(
+            render(&maze, Colours::Plain),
+            ["─└│┌┐", "┐┏━┓│", "└┃I┃│", "─┗━┛│", "└│─┘┌", ""].join("\n")
+        );
+    }
+
+    #[test]
+    pub fn test_render_counts_inside() {
+        for seed in 0..5 {
+            let maze = Day10::parse(&Day10::generate(20, seed)).unwrap();
+            let rendered = render(&maze, Colours::Plain);
+
+            assert_eq!(rendered.matches('I').count(), part_two(&maze));
+            assert_eq!(rendered.lines().count(), maze.grid.height());
+        }
+    }
+
+    #[test]
+    pub fn test_render_ansi() {
+        let maze = Day10::parse("...\nS7.\nLJ.").unwrap();
+
+        assert_eq!(
+            render(&maze, Colours::Ansi),
+            [
+                "\x1b[2m···\x1b[0m",
+                "\x1b[1;31m┏\x1b[0m\x1b[1;33m┓\x1b[0m\x1b[2m·\x1b[0m",
+                "\x1b[1;33m┗┛\x1b[0m\x1b[2m·\x1b[0m",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
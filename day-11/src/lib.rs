@@ -1,121 +1,124 @@
+use aoc_common::{seeded_rng, Answer, Generator, Grid, ParseError, Point, Solution, SolveError};
+use rand::Rng;
 use tracing::trace;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
-
-#[derive(Debug, PartialEq, Clone)]
-pub struct Position {
-    pub x: usize,
-    pub y: usize,
-}
-
-impl Position {
-    pub fn distance(&self, other: &Self) -> usize {
-        let x_dist = match self.x > other.x {
-            true => self.x - other.x,
-            false => other.x - self.x,
-        };
-
-        let y_dist = match self.y > other.y {
-            true => self.y - other.y,
-            false => other.y - self.y,
-        };
-
-        x_dist + y_dist
-    }
-}
-
-fn get_empty_row_indice(space: Vec<String>) -> Vec<usize> {
+fn get_empty_row_indice(space: &Grid<char>) -> Vec<usize> {
     space
-        .iter()
+        .rows()
         .enumerate()
-        .filter(|(_, r)| r.chars().all(|c| c == '.'))
+        .filter(|(_, r)| r.iter().all(|c| *c == '.'))
         .map(|(i, _)| i)
         .collect()
 }
 
-fn get_empty_col_indice(space: Vec<String>) -> Vec<usize> {
-    let col_count = space.first().unwrap().len();
-    (0..col_count)
-        .filter(|y| space.iter().all(|r| r.chars().nth(*y) == Some('.')))
+fn get_empty_col_indice(space: &Grid<char>) -> Vec<usize> {
+    (0..space.width())
+        .filter(|x| space.column(*x).all(|c| *c == '.'))
         .collect()
 }
 
 fn get_expanded_position(
-    x: usize,
-    y: usize,
+    galaxy: Point,
     empty_row_indice: &[usize],
     empty_col_indice: &[usize],
     factor: usize,
-) -> Position {
-    let x_dist = empty_col_indice.iter().filter(|c| *c < &x).count() * factor;
+) -> Point {
+    let x_dist = empty_col_indice.iter().filter(|c| **c < galaxy.x).count() * factor;
 
-    let y_dist = empty_row_indice.iter().filter(|c| *c < &y).count() * factor;
+    let y_dist = empty_row_indice.iter().filter(|c| **c < galaxy.y).count() * factor;
 
-    Position {
-        x: x + x_dist,
-        y: y + y_dist,
+    Point {
+        x: galaxy.x + x_dist,
+        y: galaxy.y + y_dist,
     }
 }
 
-pub fn find_galaxies_with_expansion(space: Vec<String>, factor: usize) -> Vec<Position> {
+pub fn find_galaxies_with_expansion(space: &Grid<char>, factor: usize) -> Vec<Point> {
     let factor = (factor - 1).max(1);
 
-    let empty_row_indice = get_empty_row_indice(space.clone());
-    let empty_col_indice = get_empty_col_indice(space.clone());
+    let empty_row_indice = get_empty_row_indice(space);
+    let empty_col_indice = get_empty_col_indice(space);
 
-    let galaxy_map: Vec<Vec<usize>> = space
+    let galaxies: Vec<Point> = space
         .iter()
-        .map(|s| {
-            s.char_indices()
-                .filter(|(_, c)| *c == '#')
-                .map(|(i, _)| i)
-                .collect()
-        })
+        .filter(|(_, c)| **c == '#')
+        .map(|(p, _)| p)
         .collect();
 
-    trace!(?galaxy_map);
+    trace!(?galaxies);
 
-    galaxy_map
-        .iter()
-        .enumerate()
-        .flat_map(|(y, x_positions)| {
-            x_positions
-                .iter()
-                .map(|x| get_expanded_position(*x, y, &empty_row_indice, &empty_col_indice, factor))
-                .collect::<Vec<Position>>()
-        })
+    galaxies
+        .into_iter()
+        .map(|g| get_expanded_position(g, &empty_row_indice, &empty_col_indice, factor))
         .collect()
 }
 
-fn get_shortest_path_sum(inp: Vec<String>, factor: usize) -> usize {
-    let mut galaxies = find_galaxies_with_expansion(inp, factor);
+fn get_shortest_path_sum(space: &Grid<char>, factor: usize) -> usize {
+    let mut galaxies = find_galaxies_with_expansion(space, factor);
 
     let mut output = 0;
     while let Some(galaxy) = galaxies.pop() {
         output += galaxies
             .iter()
-            .map(|og| galaxy.distance(og))
+            .map(|og| galaxy.manhattan(og))
             .sum::<usize>();
     }
 
     output
 }
 
-pub fn part_one(inp: Vec<String>) -> usize {
-    get_shortest_path_sum(inp, 1)
+pub fn part_one(space: &Grid<char>) -> usize {
+    get_shortest_path_sum(space, 1)
 }
 
-pub fn part_two(inp: Vec<String>) -> usize {
-    get_shortest_path_sum(inp, 1000000)
+pub fn part_two(space: &Grid<char>) -> usize {
+    get_shortest_path_sum(space, 1000000)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::parse_with(
+            input,
+            |c| matches!(c, '.' | '#').then_some(c),
+            "empty space `.` or galaxy `#`",
+        )
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day11 {
+    /// A `size` by `size` image with galaxies on about one in 40 tiles,
+    /// leaving about one in 20 rows and columns empty to expand.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+
+        let empty_rows = (0..size)
+            .map(|_| rng.gen_ratio(1, 20))
+            .collect::<Vec<bool>>();
+        let empty_cols = (0..size)
+            .map(|_| rng.gen_ratio(1, 20))
+            .collect::<Vec<bool>>();
+
+        let mut space = Grid::filled(size, size, '.');
+        for p in space.points().collect::<Vec<Point>>() {
+            if !empty_rows[p.y] && !empty_cols[p.x] && rng.gen_ratio(1, 40) {
+                space[p] = '#';
+            }
+        }
+
+        space.to_string()
+    }
 }
 
 #[cfg(test)]
@@ -124,7 +127,7 @@ mod test {
 
     #[test_log::test]
     pub fn test_get_shortest_path_sum() {
-        let input = vec![
+        let input = [
             "...#......".to_string(),
             ".......#..".to_string(),
             "#.........".to_string(),
@@ -137,14 +140,16 @@ mod test {
             "#...#.....".to_string(),
         ];
 
-        assert_eq!(get_shortest_path_sum(input.clone(), 1), 374);
-        assert_eq!(get_shortest_path_sum(input.clone(), 10), 1030);
-        assert_eq!(get_shortest_path_sum(input.clone(), 100), 8410);
+        let input = Day11::parse(&input.join("\n")).unwrap();
+
+        assert_eq!(get_shortest_path_sum(&input, 1), 374);
+        assert_eq!(get_shortest_path_sum(&input, 10), 1030);
+        assert_eq!(get_shortest_path_sum(&input, 100), 8410);
     }
 
     #[test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             "...#......".to_string(),
             ".......#..".to_string(),
             "#.........".to_string(),
@@ -157,14 +162,14 @@ mod test {
             "#...#.....".to_string(),
         ];
 
-        let res = part_one(input);
+        let res = part_one(&Day11::parse(&input.join("\n")).unwrap());
 
         assert_eq!(res, 374);
     }
 
     #[test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "...#......".to_string(),
             ".......#..".to_string(),
             "#.........".to_string(),
@@ -177,8 +182,20 @@ mod test {
             "#...#.....".to_string(),
         ];
 
-        let res = part_two(input);
+        let res = part_two(&Day11::parse(&input.join("\n")).unwrap());
 
         assert_eq!(res, 82000210);
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day11::generate(100, 1);
+        assert_eq!(input, Day11::generate(100, 1));
+
+        let space = Day11::parse(&input).unwrap();
+        assert_eq!((space.width(), space.height()), (100, 100));
+        assert!(!get_empty_row_indice(&space).is_empty());
+        assert!(!get_empty_col_indice(&space).is_empty());
+        assert!(part_two(&space) > part_one(&space));
+    }
 }
@@ -1,25 +1,17 @@
+use aoc_common::{seeded_rng, Answer, Generator, IResult, ParseError, Solution, SolveError};
 use cached::{proc_macro::cached, UnboundCache};
 use itertools::Itertools;
 pub(crate) use nom::{
     bytes::complete::{is_a, tag},
     character::complete::{self, space1},
+    combinator::{all_consuming, cut},
+    error::context,
     multi::separated_list1,
     sequence::separated_pair,
-    IResult,
 };
+use rand::Rng;
 use rayon::prelude::*;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
-
 #[derive(Debug, PartialEq, Clone)]
 pub struct Record {
     pub condition: String,
@@ -28,9 +20,9 @@ pub struct Record {
 
 pub fn parse_record(input: &str) -> IResult<&str, Record> {
     let (input, (condition, location)) = separated_pair(
-        is_a(".#?"),
-        space1,
-        separated_list1(tag(","), complete::u64),
+        context("spring `.`, `#` or `?`", is_a(".#?")),
+        context("spring `.`, `#`, `?` or a space", space1),
+        separated_list1(tag(","), cut(context("group size", complete::u64))),
     )(input)?;
 
     Ok((
@@ -48,7 +40,7 @@ impl Record {
     }
 
     pub fn expand(&mut self) -> &mut Self {
-        self.condition = std::iter::repeat(&self.condition).take(5).join("?");
+        self.condition = std::iter::repeat_n(&self.condition, 5).join("?");
         self.required_groups = self.required_groups.repeat(5);
         self
     }
@@ -110,33 +102,80 @@ fn num_valid_solutions(record: &str, groups: &[u64]) -> u64 {
             + num_valid_solutions(&format!(".{rest_of_record}"), groups);
     }
 
-    todo!("invalid char");
+    unreachable!("records only contain `.`, `#` and `?`");
 }
 
 #[tracing::instrument(skip_all)]
-pub fn part_one(inp: Vec<String>) -> u64 {
-    inp.iter()
-        .map(|line| {
-            parse_record(line)
-                .unwrap()
-                .1
-                .get_possible_arrangement_count()
-        })
+pub fn part_one(records: &[Record]) -> u64 {
+    records
+        .iter()
+        .map(|r| r.get_possible_arrangement_count())
         .sum()
 }
 
-pub fn part_two(inp: Vec<String>) -> u64 {
-    inp.par_iter()
-        .map(|line| {
-            parse_record(line)
-                .unwrap()
-                .1
-                .expand()
-                .get_possible_arrangement_count()
-        })
+pub fn part_two(records: &[Record]) -> u64 {
+    records
+        .par_iter()
+        .map(|r| r.clone().expand().get_possible_arrangement_count())
         .sum::<u64>()
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Vec<Record>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input
+            .lines()
+            .map(|l| {
+                all_consuming(parse_record)(l)
+                    .map(|(_, r)| r)
+                    .map_err(|e| ParseError::from_nom(input, e))
+            })
+            .collect()
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day12 {
+    /// `size` records of up to 20 springs. The groups come from a random
+    /// row of springs, of which about two in five are then made unknown.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+        let mut records = Vec::with_capacity(size);
+
+        for _ in 0..size {
+            let mut springs = (0..rng.gen_range(5..=20))
+                .map(|_| if rng.gen() { '#' } else { '.' })
+                .collect::<Vec<char>>();
+            let at = rng.gen_range(0..springs.len());
+            springs[at] = '#';
+
+            let groups = springs
+                .split(|c| *c == '.')
+                .filter(|g| !g.is_empty())
+                .map(|g| g.len().to_string())
+                .join(",");
+            let condition = springs
+                .into_iter()
+                .map(|c| if rng.gen_ratio(2, 5) { '?' } else { c })
+                .collect::<String>();
+
+            records.push(format!("{condition} {groups}"));
+        }
+
+        records.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
@@ -193,20 +232,32 @@ mod test {
         let res = parse_record(input);
 
         assert_eq!(
-            res,
-            Ok((
+            res.unwrap(),
+            (
                 "",
                 Record {
                     condition: "???.###".to_string(),
                     required_groups: vec![1, 1, 3]
                 }
-            ))
+            )
         );
     }
 
+    #[test_log::test]
+    pub fn test_parse_error() {
+        let res = Day12::parse("???.### 1,1,3\n.??x.??...?##. 1,1,3");
+        assert_eq!(
+            res.err(),
+            Some(ParseError::new(2, 4, "spring `.`, `#`, `?` or a space"))
+        );
+
+        let res = Day12::parse("???.### 1,,3");
+        assert_eq!(res.err(), Some(ParseError::new(1, 11, "group size")));
+    }
+
     #[test_log::test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             "???.### 1,1,3".to_string(),
             ".??..??...?##. 1,1,3".to_string(),
             "?#?#?#?#?#?#?#? 1,3,1,6".to_string(),
@@ -215,14 +266,16 @@ mod test {
             "?###???????? 3,2,1".to_string(),
         ];
 
-        let res = part_one(input);
+        let records = Day12::parse(&input.join("\n")).unwrap();
+
+        let res = part_one(&records);
 
         assert_eq!(res, 21);
     }
 
     #[test_log::test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "???.### 1,1,3".to_string(),
             ".??..??...?##. 1,1,3".to_string(),
             "?#?#?#?#?#?#?#? 1,3,1,6".to_string(),
@@ -231,8 +284,22 @@ mod test {
             "?###???????? 3,2,1".to_string(),
         ];
 
-        let res = part_two(input);
+        let records = Day12::parse(&input.join("\n")).unwrap();
+
+        let res = part_two(&records);
 
         assert_eq!(res, 525152);
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day12::generate(50, 1);
+        assert_eq!(input, Day12::generate(50, 1));
+
+        let records = Day12::parse(&input).unwrap();
+        assert_eq!(records.len(), 50);
+        assert!(records
+            .iter()
+            .all(|r| r.get_possible_arrangement_count() > 0));
+    }
 }
@@ -1,19 +1,10 @@
+use aoc_common::{seeded_rng, Answer, Generator, Grid, ParseError, Solution, SolveError};
 use itertools::Itertools;
+use rand::Rng;
 use tracing::trace;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
-
-    res
-}
-
-pub fn detect_horizontal_fold(inp: &[String], with_smudge: bool) -> Option<usize> {
-    inp.iter()
+pub fn detect_horizontal_fold(inp: &Grid<char>, with_smudge: bool) -> Option<usize> {
+    inp.rows()
         .enumerate()
         .tuple_windows()
         .filter(|((_, a), (_, b))| {
@@ -21,11 +12,11 @@ pub fn detect_horizontal_fold(inp: &[String], with_smudge: bool) -> Option<usize
                 return a == b;
             }
 
-            a.chars().zip(b.chars()).filter(|(c, d)| c != d).count() <= 1
+            a.iter().zip(b.iter()).filter(|(c, d)| c != d).count() <= 1
         })
         .find_map(|(a, b)| {
-            let inp_a = inp[0..=a.0].iter().map(|l| l.chars()).rev();
-            let inp_b = inp[b.0..inp.len()].iter().map(|l| l.chars());
+            let inp_a = (0..=a.0).rev().flat_map(|y| inp.row(y));
+            let inp_b = (b.0..inp.height()).flat_map(|y| inp.row(y));
 
             let c = match with_smudge {
                 true => 1,
@@ -33,8 +24,7 @@ pub fn detect_horizontal_fold(inp: &[String], with_smudge: bool) -> Option<usize
             };
 
             inp_a
-                .flatten()
-                .zip(inp_b.flatten())
+                .zip(inp_b)
                 .filter(|(a, b)| a != b)
                 .count()
                 .eq(&c)
@@ -42,70 +32,109 @@ pub fn detect_horizontal_fold(inp: &[String], with_smudge: bool) -> Option<usize
         })
 }
 
-pub fn detect_vertical_fold(inp: &[String], with_smudge: bool) -> Option<usize> {
-    let inp: Vec<String> = (0..inp[0].len())
-        .map(|i| {
-            inp.iter()
-                .map(|inner| inner.chars().nth(i).unwrap())
-                .join("")
-        })
-        .collect();
-
-    detect_horizontal_fold(&inp, with_smudge)
+pub fn detect_vertical_fold(inp: &Grid<char>, with_smudge: bool) -> Option<usize> {
+    detect_horizontal_fold(&inp.transpose(), with_smudge)
 }
 
-pub fn get_fold_pos(inp: Vec<String>) -> Option<usize> {
-    match detect_vertical_fold(&inp, false) {
+pub fn get_fold_pos(inp: &Grid<char>) -> Option<usize> {
+    match detect_vertical_fold(inp, false) {
         Some(res) => Some(res),
-        None => detect_horizontal_fold(&inp, false).map(|x| x * 100),
+        None => detect_horizontal_fold(inp, false).map(|x| x * 100),
     }
 }
 
 #[tracing::instrument(skip_all)]
-pub fn get_smudge_fold_pos(inp: Vec<String>) -> Option<usize> {
-    match detect_horizontal_fold(&inp, true) {
+pub fn get_smudge_fold_pos(inp: &Grid<char>) -> Option<usize> {
+    match detect_horizontal_fold(inp, true) {
         Some(res) => Some(res * 100),
-        None => detect_vertical_fold(&inp, true),
+        None => detect_vertical_fold(inp, true),
     }
 }
 
-pub fn part_one(inp: Vec<String>) -> usize {
-    inp.into_iter()
-        .fold(vec![vec![]], |mut acc, x| {
-            if x.is_empty() {
-                acc.push(vec![]);
+/// Parses the blank line separated patterns, reporting errors relative to
+/// the whole input.
+pub fn parse_patterns(inp: &str) -> Result<Vec<Grid<char>>, ParseError> {
+    let lines: Vec<&str> = inp.lines().collect();
 
-                return acc;
-            }
+    let mut start = 0;
+    let mut patterns = vec![];
+    for block in lines.split(|l| l.is_empty()) {
+        let pattern = Grid::parse_with(
+            &block.join("\n"),
+            |c| matches!(c, '.' | '#').then_some(c),
+            "ash `.` or rock `#`",
+        )
+        .map_err(|e| ParseError::new(e.line + start, e.column, e.expected))?;
 
-            acc.last_mut().unwrap().push(x);
+        patterns.push(pattern);
+        start += block.len() + 1;
+    }
 
-            acc
-        })
-        .into_iter()
-        .filter_map(get_fold_pos)
-        .sum::<usize>()
+    Ok(patterns)
+}
+
+pub fn part_one(patterns: &[Grid<char>]) -> usize {
+    patterns.iter().filter_map(get_fold_pos).sum::<usize>()
 }
 
 #[tracing::instrument(skip_all)]
-pub fn part_two(inp: Vec<String>) -> usize {
-    let lines = inp.into_iter().fold(vec![vec![]], |mut acc, x| {
-        if x.is_empty() {
-            acc.push(vec![]);
+pub fn part_two(patterns: &[Grid<char>]) -> usize {
+    patterns
+        .iter()
+        .filter_map(get_smudge_fold_pos)
+        .inspect(|x| trace!("x: {:?}", x))
+        .sum::<usize>()
+}
 
-            return acc;
-        }
+pub struct Day13;
 
-        acc.last_mut().unwrap().push(x);
+impl Solution for Day13 {
+    type Input<'a> = Vec<Grid<char>>;
 
-        acc
-    });
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_patterns(input)
+    }
 
-    return lines
-        .into_iter()
-        .filter_map(get_smudge_fold_pos)
-        .inspect(|x| trace!("x: {:?}", x))
-        .sum::<usize>();
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day13 {
+    /// `size` patterns of five to 17 rows and columns, each mirrored along
+    /// a random horizontal or vertical line.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+        let mut patterns = Vec::with_capacity(size);
+
+        for _ in 0..size {
+            let (width, height) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+            let fold = rng.gen_range(1..height);
+
+            let mut rows: Vec<Vec<char>> = Vec::with_capacity(height);
+            for y in 0..height {
+                let row = match (2 * fold).checked_sub(y + 1) {
+                    Some(mirrored) if y >= fold => rows[mirrored].clone(),
+                    _ => (0..width)
+                        .map(|_| if rng.gen() { '#' } else { '.' })
+                        .collect(),
+                };
+                rows.push(row);
+            }
+
+            let pattern = Grid::from_rows(rows).unwrap();
+            patterns.push(match rng.gen() {
+                true => pattern.transpose().to_string(),
+                false => pattern.to_string(),
+            });
+        }
+
+        patterns.join("\n\n")
+    }
 }
 
 #[cfg(test)]
@@ -113,6 +142,10 @@ mod test {
     use crate::*;
     use rstest::rstest;
 
+    fn grid(input: &[String]) -> Grid<char> {
+        input.join("\n").parse().unwrap()
+    }
+
     #[rstest]
     #[case(
         vec![
@@ -158,7 +191,7 @@ mod test {
     )]
     #[test_log::test]
     pub fn test_get_smudge_fold_pos(#[case] input: Vec<String>, #[case] expected: Option<usize>) {
-        assert_eq!(get_smudge_fold_pos(input), expected);
+        assert_eq!(get_smudge_fold_pos(&grid(&input)), expected);
     }
 
     #[rstest]
@@ -188,7 +221,7 @@ mod test {
     )]
     #[test_log::test]
     pub fn test_get_fold_pos(#[case] input: Vec<String>, #[case] expected: Option<usize>) {
-        assert_eq!(get_fold_pos(input), expected);
+        assert_eq!(get_fold_pos(&grid(&input)), expected);
     }
 
     #[rstest]
@@ -218,7 +251,7 @@ mod test {
     )]
     #[test_log::test]
     pub fn test_detect_vertical_fold(#[case] input: Vec<String>, #[case] expected: Option<usize>) {
-        assert_eq!(detect_vertical_fold(&input, false), expected);
+        assert_eq!(detect_vertical_fold(&grid(&input), false), expected);
     }
 
     #[rstest]
@@ -251,12 +284,19 @@ mod test {
         #[case] input: Vec<String>,
         #[case] expected: Option<usize>,
     ) {
-        assert_eq!(detect_horizontal_fold(&input, false), expected);
+        assert_eq!(detect_horizontal_fold(&grid(&input), false), expected);
+    }
+
+    #[test_log::test]
+    pub fn test_parse_patterns_error() {
+        let res = parse_patterns("#.#\n.#.\n\n##.\n.x#");
+
+        assert_eq!(res, Err(ParseError::new(5, 2, "ash `.` or rock `#`")));
     }
 
     #[test_log::test]
     pub fn test_part_one() {
-        let input = vec![
+        let input = [
             "#.##..##.".to_string(),
             "..#.##.#.".to_string(),
             "##......#".to_string(),
@@ -274,14 +314,16 @@ mod test {
             "#....#..#".to_string(),
         ];
 
-        let res = part_one(input);
+        let patterns = parse_patterns(&input.join("\n")).unwrap();
+
+        let res = part_one(&patterns);
 
         assert_eq!(res, 405);
     }
 
     #[test_log::test]
     pub fn test_part_two() {
-        let input = vec![
+        let input = [
             "#.##..##.".to_string(),
             "..#.##.#.".to_string(),
             "##......#".to_string(),
@@ -299,8 +341,20 @@ mod test {
             "#....#..#".to_string(),
         ];
 
-        let res = part_two(input);
+        let patterns = parse_patterns(&input.join("\n")).unwrap();
+
+        let res = part_two(&patterns);
 
         assert_eq!(res, 400);
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day13::generate(50, 1);
+        assert_eq!(input, Day13::generate(50, 1));
+
+        let patterns = Day13::parse(&input).unwrap();
+        assert_eq!(patterns.len(), 50);
+        assert!(patterns.iter().all(|p| get_fold_pos(p).is_some()));
+    }
 }
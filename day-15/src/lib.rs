@@ -1,80 +1,83 @@
+use aoc_common::{
+    parse::{finish, unsigned},
+    seeded_rng, Answer, Generator, IResult, ParseError, Solution, SolveError,
+};
+use nom::{
+    branch::alt,
+    character::complete::{alpha1, char},
+    combinator::{consumed, cut, map, value},
+    error::context,
+    multi::separated_list1,
+    sequence::{pair, preceded},
+};
+use rand::Rng;
 use tracing::trace;
 
-pub fn read_lines() -> Vec<String> {
-    let mut res = Vec::new();
-
-    let content = include_str!("../input.txt");
-    for line in content.lines() {
-        res.push(line.to_string());
-    }
+pub fn hash(inp: &str) -> u8 {
+    inp.chars().fold(0, |hash, c| (hash + c as u64) * 17 % 256) as u8
+}
 
-    res
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Operation {
+    /// `label=n`, putting a lens of focal length `n` into the box.
+    Insert(u8),
+    /// `label-`, taking the lens out of the box.
+    Remove,
 }
 
-pub fn hash(inp: &str) -> u8 {
-    inp.chars().fold(0, |hash, c| (hash + c as u64) * 17 % 256) as u8
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Step<'a> {
+    /// The whole step as written, which part one hashes.
+    pub text: &'a str,
+    pub label: &'a str,
+    pub operation: Operation,
 }
 
-pub fn part_one(inp: Vec<String>) -> u64 {
-    let inp = inp[0].split(',').collect::<Vec<_>>();
+pub fn step(input: &str) -> IResult<&str, Step<'_>> {
+    map(
+        consumed(pair(
+            context("label", alpha1),
+            alt((
+                map(preceded(char('='), cut(unsigned)), Operation::Insert),
+                value(Operation::Remove, char('-')),
+            )),
+        )),
+        |(text, (label, operation))| Step {
+            text,
+            label,
+            operation,
+        },
+    )(input)
+}
 
-    inp.iter().map(|s| hash(s) as u64).sum()
+pub fn parse_steps(input: &str) -> IResult<&str, Vec<Step<'_>>> {
+    separated_list1(char(','), cut(step))(input)
 }
 
-pub fn part_two(inp: Vec<String>) -> u64 {
-    let inp = inp[0].split(',').collect::<Vec<&str>>();
+pub fn part_one(inp: &[Step]) -> u64 {
+    inp.iter().map(|s| hash(s.text) as u64).sum()
+}
 
+pub fn part_two(inp: &[Step]) -> u64 {
     let mut map: [Vec<(&str, u8)>; 256] = vec![Vec::new(); 256].try_into().expect("static");
 
-    for inp in inp.iter() {
-        if inp.contains('=') {
-            let parts = inp.split('=').collect::<Vec<&str>>();
-            let box_id = hash(parts[0]);
+    for step in inp.iter() {
+        let lenses = &mut map[hash(step.label) as usize];
+        let position = lenses.iter().position(|(k, _)| *k == step.label);
 
+        match (step.operation, position) {
+            // replace, if exists
+            (Operation::Insert(focal_length), Some(i)) => lenses[i].1 = focal_length,
             // insert, if not exists
-            if map[box_id as usize]
-                .iter()
-                .map(|(k, _)| k)
-                .filter(|k| **k == parts[0])
-                .count()
-                == 0
-            {
-                map[box_id as usize].push((parts[0], parts[1].parse::<u8>().unwrap()));
-
-                continue;
+            (Operation::Insert(focal_length), None) => lenses.push((step.label, focal_length)),
+            // remove label from box
+            (Operation::Remove, Some(i)) => {
+                lenses.remove(i);
             }
-
-            // replace, if exists
-            map[box_id as usize] = map[box_id as usize]
-                .clone()
-                .into_iter()
-                .map(|mut lens| {
-                    if lens.0 == parts[0] {
-                        lens.1 = parts[1].parse::<u8>().unwrap();
-                    }
-
-                    lens
-                })
-                .collect();
-            trace!("= {:?}", map[box_id as usize]);
+            (Operation::Remove, None) => {}
         }
 
-        if inp.contains('-') {
-            let parts = inp.split('-').collect::<Vec<&str>>();
-            let box_id = hash(parts[0]);
-
-            // remove labels from box
-            map[box_id as usize]
-                .clone()
-                .into_iter()
-                .enumerate()
-                .filter_map(|(i, lens)| if lens.0 == parts[0] { Some(i) } else { None })
-                .for_each(|id| {
-                    map[box_id as usize].remove(id);
-                });
-
-            trace!("- {:?}", map[box_id as usize]);
-        }
+        trace!("{} {:?}", step.text, lenses);
     }
 
     map.iter()
@@ -88,6 +91,53 @@ pub fn part_two(inp: Vec<String>) -> u64 {
         .sum::<u64>()
 }
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<Step<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        finish(input, parse_steps)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part_two(input).into())
+    }
+}
+
+impl Generator for Day15 {
+    /// `size` steps inserting or removing lenses, with labels drawn from a
+    /// pool of a quarter as many labels so they repeat.
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = seeded_rng(seed);
+
+        let labels = (0..(size / 4).max(1))
+            .map(|_| {
+                (0..rng.gen_range(2..=6))
+                    .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+
+        let steps = (0..size)
+            .map(|_| {
+                let label = &labels[rng.gen_range(0..labels.len())];
+
+                match rng.gen_ratio(3, 5) {
+                    true => format!("{label}={}", rng.gen_range(1..=9)),
+                    false => format!("{label}-"),
+                }
+            })
+            .collect::<Vec<String>>();
+
+        steps.join(",")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
@@ -105,19 +155,68 @@ mod test {
 
     #[test]
     pub fn test_part_one() {
-        let input = vec!["rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7".to_string()];
+        let input = Day15::parse("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap();
 
-        let res = part_one(input);
+        let res = part_one(&input);
 
         assert_eq!(res, 1320);
     }
 
     #[test_log::test]
     pub fn test_part_two() {
-        let input = vec!["rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7".to_string()];
+        let input = Day15::parse("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap();
 
-        let res = part_two(input);
+        let res = part_two(&input);
 
         assert_eq!(res, 145);
     }
+
+    #[test]
+    pub fn test_generate() {
+        let input = Day15::generate(50, 1);
+        assert_eq!(input, Day15::generate(50, 1));
+
+        let steps = Day15::parse(&input).unwrap();
+        assert_eq!(steps.len(), 50);
+        assert!(steps.iter().all(|s| s.text.starts_with(s.label)));
+    }
+
+    #[test]
+    pub fn test_parse_steps() {
+        assert_eq!(
+            Day15::parse("rn=1,cm-\n").unwrap(),
+            [
+                Step {
+                    text: "rn=1",
+                    label: "rn",
+                    operation: Operation::Insert(1),
+                },
+                Step {
+                    text: "cm-",
+                    label: "cm",
+                    operation: Operation::Remove,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Day15::parse("rn=x,cm-").err(),
+            Some(ParseError::new(1, 4, "number"))
+        );
+        assert_eq!(
+            Day15::parse("rn=1,cm+").err(),
+            Some(ParseError::new(1, 8, "`=` or `-`"))
+        );
+        assert_eq!(
+            Day15::parse("rn=1,cm=300").err(),
+            Some(ParseError::new(1, 9, "number fitting into `u8`"))
+        );
+        assert_eq!(
+            Day15::parse("rn=1,=2").err(),
+            Some(ParseError::new(1, 6, "label"))
+        );
+    }
 }